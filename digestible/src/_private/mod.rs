@@ -1,6 +1,76 @@
 /*!
-Exports from core that are used in the macro
+Exports from core and helpers that are used in the macro
 */
 
 pub use core::any::type_name;
+pub use core::clone::Clone;
+pub use core::convert::{From, Into};
 pub use core::hash::{Hash, Hasher};
+pub use core::mem::size_of;
+
+use crate::format::{write_bytes, Format};
+use crate::sha256::HmacSha256;
+use crate::DigestWriter;
+use byteorder::ByteOrder;
+
+/// Writes the type name of `T`. V1 writes the length first
+#[inline(always)]
+pub fn write_type_header<T: ?Sized, B: ByteOrder, W: DigestWriter>(writer: &mut W) {
+    write_bytes::<B, W>(writer, type_name::<T>().as_bytes());
+}
+/// Legacy writes the type name of `T` followed by `::`. V1 is the same as [write_type_header]
+#[inline(always)]
+pub fn write_enum_header<T: ?Sized, B: ByteOrder, W: DigestWriter>(writer: &mut W) {
+    write_type_header::<T, B, W>(writer);
+    if !crate::format::is_v1::<W>() {
+        writer.write(b"::");
+    }
+}
+/// Writes the name of the variant. V1 writes the length first
+#[inline(always)]
+pub fn write_variant_name<B: ByteOrder, W: DigestWriter>(writer: &mut W, name: &str) {
+    write_bytes::<B, W>(writer, name.as_bytes());
+}
+/// Writes the presence marker of `redact` fields
+#[inline(always)]
+pub fn write_presence<W: DigestWriter>(writer: &mut W, present: bool) {
+    writer.write_u8(present as u8);
+}
+/// Writes the HMAC of the field for `salted` and `keyed` fields. See [keyed](crate::keyed)
+///
+/// Without a key only the presence marker is written
+pub fn digest_keyed<W: DigestWriter>(
+    writer: &mut W,
+    context: &str,
+    digest: impl FnOnce(&mut KeyedFieldWriter<'_, W>),
+) {
+    let Some(key) = writer.digest_key() else {
+        return write_presence(writer, true);
+    };
+    let mut mac = HmacSha256::new(key);
+    mac.update(&(context.len() as u64).to_le_bytes());
+    mac.update(context.as_bytes());
+    let mut field_writer = KeyedFieldWriter {
+        parent: writer,
+        mac,
+    };
+    digest(&mut field_writer);
+    let mac = field_writer.mac.finalize();
+    writer.write(&mac);
+}
+/// Digests a field into the HMAC. Uses the format and key of the parent
+pub struct KeyedFieldWriter<'p, W> {
+    parent: &'p W,
+    mac: HmacSha256,
+}
+impl<W: DigestWriter> DigestWriter for KeyedFieldWriter<'_, W> {
+    const FORMAT: Format = W::FORMAT;
+    #[inline(always)]
+    fn write(&mut self, data: &[u8]) {
+        self.mac.update(data);
+    }
+    #[inline(always)]
+    fn digest_key(&self) -> Option<&[u8]> {
+        self.parent.digest_key()
+    }
+}
@@ -0,0 +1,224 @@
+/*!
+# Digest Based Equality
+
+[ByDigest] digests the value once when it is created.
+Equality, ordering and hashing then only use the stored digest.
+So large values can be used as keys of a `HashMap` or `BTreeMap` without comparing them field by field.
+
+Two values are equal if their digests are equal.
+The value is digested with [FormatV1] by default. So different values of the same type never have the same encoding.
+With [Legacy](crate::format::Legacy) `vec!["a", "bc"]` and `vec!["ab", "c"]` would be equal.
+The value can not be mutated. Otherwise the stored digest would no longer match it.
+
+The digest is computed without the key of [keyed](crate::keyed) fields. They are written as presence markers.
+Digesting a ByDigest with a [KeyedWriter](crate::keyed::KeyedWriter) writes the same stored digest.
+Use [Cached](crate::cached::Cached) if the key should be used.
+
+## Example
+```rust
+use digestible::by_digest::ByDigest;
+use digestible::Digestible;
+use sha2::Sha256;
+use std::collections::HashMap;
+#[derive(Digestible)]
+pub struct Document {
+    pub title: String,
+    pub body: String,
+}
+let mut seen: HashMap<ByDigest<Document, Sha256>, u32> = HashMap::new();
+let document = Document {
+    title: "Hello".to_string(),
+    body: "World".to_string(),
+};
+*seen.entry(ByDigest::new(document)).or_default() += 1;
+
+let same = ByDigest::new(Document {
+    title: "Hello".to_string(),
+    body: "World".to_string(),
+});
+assert_eq!(seen.get(&same), Some(&1));
+assert_eq!(same.title, "Hello");
+```
+*/
+use crate::format::{EncodingFormat, FormatV1};
+use crate::{DigestWriter, Digester, Digestible};
+use byteorder::{ByteOrder, NativeEndian};
+use core::cmp::Ordering;
+use core::fmt::{Debug, Formatter};
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::ops::Deref;
+
+/// A value with its digest. Compared and hashed by the digest. See [module docs](self)
+pub struct ByDigest<T, D: Digester, B: ByteOrder = NativeEndian, F: EncodingFormat = FormatV1> {
+    value: T,
+    digest: D::Target,
+    _encoding: PhantomData<fn() -> (B, F)>,
+}
+impl<T: Digestible, D: Digester, B: ByteOrder, F: EncodingFormat> ByDigest<T, D, B, F> {
+    /// Digests the value with the default Digester
+    pub fn new(value: T) -> Self
+    where
+        D: Default,
+    {
+        Self::with_digester(value, D::default())
+    }
+    /// Digests the value with the given Digester
+    pub fn with_digester(value: T, digester: D) -> Self {
+        let digest = digester.digest_v::<F, B>(&value);
+        Self {
+            value,
+            digest,
+            _encoding: PhantomData,
+        }
+    }
+}
+impl<T, D: Digester, B: ByteOrder, F: EncodingFormat> ByDigest<T, D, B, F> {
+    /// The value
+    pub fn get(&self) -> &T {
+        &self.value
+    }
+    /// The stored digest
+    pub fn digest(&self) -> &D::Target {
+        &self.digest
+    }
+    /// Returns the value
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+    /// Returns the value and its digest
+    pub fn into_parts(self) -> (T, D::Target) {
+        (self.value, self.digest)
+    }
+}
+/// Writes the stored digest
+impl<T, D, B, F> Digestible for ByDigest<T, D, B, F>
+where
+    D: Digester,
+    D::Target: AsRef<[u8]>,
+    B: ByteOrder,
+    F: EncodingFormat,
+{
+    fn digest<Order: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
+        writer.write(self.digest.as_ref());
+    }
+}
+impl<T, D: Digester, B: ByteOrder, F: EncodingFormat> Deref for ByDigest<T, D, B, F> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+impl<T, D: Digester, B: ByteOrder, F: EncodingFormat> AsRef<T> for ByDigest<T, D, B, F> {
+    fn as_ref(&self) -> &T {
+        &self.value
+    }
+}
+impl<T: Digestible, D: Digester + Default, B: ByteOrder, F: EncodingFormat> From<T>
+    for ByDigest<T, D, B, F>
+{
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+impl<T: Clone, D: Digester, B: ByteOrder, F: EncodingFormat> Clone for ByDigest<T, D, B, F>
+where
+    D::Target: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            value: self.value.clone(),
+            digest: self.digest.clone(),
+            _encoding: PhantomData,
+        }
+    }
+}
+impl<T: Debug, D: Digester, B: ByteOrder, F: EncodingFormat> Debug for ByDigest<T, D, B, F>
+where
+    D::Target: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ByDigest")
+            .field("value", &self.value)
+            .field("digest", &self.digest)
+            .finish()
+    }
+}
+/// Only compares the digests
+impl<T, D: Digester, B: ByteOrder, F: EncodingFormat> PartialEq for ByDigest<T, D, B, F>
+where
+    D::Target: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.digest == other.digest
+    }
+}
+impl<T, D: Digester, B: ByteOrder, F: EncodingFormat> Eq for ByDigest<T, D, B, F> where D::Target: Eq
+{}
+/// Only compares the digests
+impl<T, D: Digester, B: ByteOrder, F: EncodingFormat> PartialOrd for ByDigest<T, D, B, F>
+where
+    D::Target: Ord,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+/// Only compares the digests
+impl<T, D: Digester, B: ByteOrder, F: EncodingFormat> Ord for ByDigest<T, D, B, F>
+where
+    D::Target: Ord,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.digest.cmp(&other.digest)
+    }
+}
+/// Only hashes the digest
+impl<T, D: Digester, B: ByteOrder, F: EncodingFormat> Hash for ByDigest<T, D, B, F>
+where
+    D::Target: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.digest.hash(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ByDigest;
+    use crate::format::FormatV1;
+    use crate::hash_digester::DigestBuildHasher;
+    use crate::Digester;
+    use byteorder::LittleEndian;
+    use sha2::{Digest, Sha256};
+    use std::collections::{BTreeSet, HashSet};
+
+    type Key = ByDigest<Vec<String>, Sha256, LittleEndian>;
+    fn key(items: &[&str]) -> Key {
+        ByDigest::new(items.iter().map(|item| item.to_string()).collect())
+    }
+
+    #[test]
+    fn compares_digests() {
+        let a = key(&["a", "b"]);
+        assert_eq!(a, key(&["a", "b"]));
+        assert_ne!(a, key(&["b", "a"]));
+        assert_eq!(
+            a.digest(),
+            &Sha256::new()
+                .digest_v::<FormatV1, LittleEndian>(&vec!["a".to_string(), "b".to_string()])
+        );
+        assert_eq!(a.len(), 2);
+        // The same bytes in Legacy
+        assert_ne!(key(&["a", "bc"]), key(&["ab", "c"]));
+
+        let set: HashSet<Key, DigestBuildHasher> = [key(&["a"]), key(&["b"]), key(&["a"])]
+            .into_iter()
+            .collect();
+        assert_eq!(set.len(), 2);
+        let ordered: BTreeSet<Key> = [key(&["a"]), key(&["b"]), key(&["a"])]
+            .into_iter()
+            .collect();
+        assert_eq!(ordered.len(), 2);
+    }
+}
@@ -0,0 +1,244 @@
+/*!
+# Cached Digests
+
+[Cached] stores the digest of the value inside of it.
+The value is digested once with the [Digester] `D`, the [ByteOrder] `B` and the [format](crate::format) `F`.
+After that the stored digest is written into the parent writer instead of digesting the value again.
+
+The stored digest is cleared on every mutable access through [DerefMut].
+
+The ByteOrder and format of the parent writer are not used for the value.
+The same as the `endian` attribute of the derive macro.
+
+The stored digest is computed without the key of [keyed](crate::keyed) fields.
+If the parent writer has a key the value is digested again with it every time. Nothing is stored.
+
+[SyncCached] is the same but can be shared between threads.
+
+## Example
+```rust
+use digestible::cached::Cached;
+use digestible::{Digester, Digestible};
+use sha2::{Digest, Sha256};
+use byteorder::LittleEndian;
+#[derive(Digestible)]
+pub struct Config {
+    pub version: u32,
+    pub routes: Cached<Vec<String>, Sha256, LittleEndian>,
+}
+let mut config = Config {
+    version: 1,
+    routes: Cached::new(vec!["/".to_string(), "/about".to_string()]),
+};
+// The routes are digested once
+let first = Sha256::new().digest::<LittleEndian>(&config);
+let second = Sha256::new().digest::<LittleEndian>(&config);
+assert_eq!(first, second);
+
+// Mutable access clears the stored digest
+config.routes.push("/contact".to_string());
+assert_ne!(Sha256::new().digest::<LittleEndian>(&config), first);
+```
+*/
+use crate::format::{EncodingFormat, Legacy};
+use crate::keyed::WithKey;
+use crate::{DigestWriter, Digester, Digestible};
+use byteorder::{ByteOrder, NativeEndian};
+use core::cell::OnceCell;
+use core::fmt::{Debug, Formatter};
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+
+macro_rules! cached_type {
+    ($(#[$docs:meta])* $name:ident, $cell:ident) => {
+        $(#[$docs])*
+        pub struct $name<T, D: Digester, B: ByteOrder = NativeEndian, F: EncodingFormat = Legacy> {
+            value: T,
+            digest: $cell<D::Target>,
+            _digester: PhantomData<fn() -> (D, B, F)>,
+        }
+        impl<T, D: Digester, B: ByteOrder, F: EncodingFormat> $name<T, D, B, F> {
+            /// Wraps the value. Nothing is digested until the digest is needed
+            pub const fn new(value: T) -> Self {
+                Self {
+                    value,
+                    digest: $cell::new(),
+                    _digester: PhantomData,
+                }
+            }
+            /// Returns the value
+            pub fn into_inner(self) -> T {
+                self.value
+            }
+            /// The stored digest. If it was computed already
+            pub fn get_digest(&self) -> Option<&D::Target> {
+                self.digest.get()
+            }
+            /// Clears the stored digest
+            pub fn invalidate(&mut self) {
+                self.digest.take();
+            }
+        }
+        impl<T: Digestible, D: Digester + Default, B: ByteOrder, F: EncodingFormat>
+            $name<T, D, B, F>
+        {
+            /// The stored digest. Digests the value if it was not computed yet
+            pub fn cached_digest(&self) -> &D::Target {
+                self.digest
+                    .get_or_init(|| D::default().digest_v::<F, B>(&self.value))
+            }
+        }
+        /// Writes the stored digest. With a key the value is digested again with the key
+        impl<T, D, B, F> Digestible for $name<T, D, B, F>
+        where
+            T: Digestible,
+            D: Digester + Default,
+            D::Target: AsRef<[u8]>,
+            B: ByteOrder,
+            F: EncodingFormat,
+        {
+            fn digest<Order: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
+                match writer.digest_key() {
+                    Some(key) => {
+                        let value = WithKey {
+                            key,
+                            value: &self.value,
+                        };
+                        let digest = D::default().digest_v::<F, B>(&value);
+                        writer.write(digest.as_ref());
+                    }
+                    None => writer.write(self.cached_digest().as_ref()),
+                }
+            }
+        }
+        impl<T, D: Digester, B: ByteOrder, F: EncodingFormat> Deref for $name<T, D, B, F> {
+            type Target = T;
+            fn deref(&self) -> &T {
+                &self.value
+            }
+        }
+        /// Clears the stored digest
+        impl<T, D: Digester, B: ByteOrder, F: EncodingFormat> DerefMut for $name<T, D, B, F> {
+            fn deref_mut(&mut self) -> &mut T {
+                self.invalidate();
+                &mut self.value
+            }
+        }
+        impl<T, D: Digester, B: ByteOrder, F: EncodingFormat> From<T> for $name<T, D, B, F> {
+            fn from(value: T) -> Self {
+                Self::new(value)
+            }
+        }
+        impl<T: Default, D: Digester, B: ByteOrder, F: EncodingFormat> Default
+            for $name<T, D, B, F>
+        {
+            fn default() -> Self {
+                Self::new(T::default())
+            }
+        }
+        impl<T: Clone, D: Digester, B: ByteOrder, F: EncodingFormat> Clone for $name<T, D, B, F>
+        where
+            D::Target: Clone,
+        {
+            fn clone(&self) -> Self {
+                Self {
+                    value: self.value.clone(),
+                    digest: self.digest.clone(),
+                    _digester: PhantomData,
+                }
+            }
+        }
+        impl<T: Debug, D: Digester, B: ByteOrder, F: EncodingFormat> Debug for $name<T, D, B, F>
+        where
+            D::Target: Debug,
+        {
+            fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+                f.debug_struct(stringify!($name))
+                    .field("value", &self.value)
+                    .field("digest", &self.digest.get())
+                    .finish()
+            }
+        }
+        /// Only compares the values
+        impl<T: PartialEq, D: Digester, B: ByteOrder, F: EncodingFormat> PartialEq
+            for $name<T, D, B, F>
+        {
+            fn eq(&self, other: &Self) -> bool {
+                self.value == other.value
+            }
+        }
+        impl<T: Eq, D: Digester, B: ByteOrder, F: EncodingFormat> Eq for $name<T, D, B, F> {}
+        /// Only hashes the value
+        impl<T: Hash, D: Digester, B: ByteOrder, F: EncodingFormat> Hash for $name<T, D, B, F> {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                self.value.hash(state)
+            }
+        }
+    };
+}
+cached_type!(
+    /// Stores the digest of the value. See [module docs](self)
+    Cached,
+    OnceCell
+);
+#[cfg(feature = "std")]
+use std::sync::OnceLock;
+#[cfg(feature = "std")]
+cached_type!(
+    /// Stores the digest of the value. Can be shared between threads. See [module docs](self)
+    ///
+    /// Only one thread digests the value. Other threads wait for it
+    SyncCached,
+    OnceLock
+);
+
+#[cfg(test)]
+mod tests {
+    use super::{Cached, SyncCached};
+    use crate::{DigestWriter, Digester, Digestible};
+    use byteorder::{ByteOrder, LittleEndian};
+    use core::cell::Cell;
+    use sha2::{Digest, Sha256};
+
+    /// Counts how many times it was digested
+    #[derive(Default)]
+    struct Counter(Cell<u32>);
+    impl Digestible for Counter {
+        fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
+            self.0.set(self.0.get() + 1);
+            writer.write_u32::<B>(7);
+        }
+    }
+
+    #[test]
+    fn digests_once() {
+        let mut cached: Cached<Counter, Sha256, LittleEndian> = Cached::default();
+        assert!(cached.get_digest().is_none());
+        let mut parent = Vec::new();
+        cached.digest::<LittleEndian, _>(&mut parent);
+        cached.digest::<LittleEndian, _>(&mut parent);
+        assert_eq!(cached.0.get(), 1);
+
+        let expected = Sha256::new().digest::<LittleEndian>(&7u32);
+        assert_eq!(&parent[..32], expected.as_slice());
+        assert_eq!(&parent[32..], expected.as_slice());
+
+        // Mutable access clears the digest
+        let _ = &mut cached.0;
+        assert!(cached.get_digest().is_none());
+        Sha256::new().digest_native(&cached);
+        assert_eq!(cached.0.get(), 2);
+    }
+
+    #[test]
+    fn sync() {
+        let cached: SyncCached<Vec<u32>, Sha256> = SyncCached::new(vec![1, 2, 3]);
+        let expected = Sha256::new().digest_native(&vec![1u32, 2, 3]);
+        std::thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| assert_eq!(cached.cached_digest(), &expected));
+            }
+        });
+    }
+}
@@ -0,0 +1,432 @@
+/*!
+# Content Addressable Storage
+
+Stores bytes under the hash of the bytes. Requires the `cas` feature.
+
+[ContentStore::put] writes the value into a buffer using [FormatV1] and LittleEndian.
+The buffer is stored under its hash with the [Digest] `Self::Digest`.
+So the key is the same as `Self::Digest::new().digest_v::<FormatV1, LittleEndian>(&value)`.
+
+[ContentStore::put_bytes] stores bytes as is. The key is the hash of the bytes.
+
+[ContentStore::get] hashes the stored bytes again and returns [CasError::Corrupted] if they no longer match the key.
+
+## Implementations
+- [MemoryStore] keeps the entries in a `HashMap`
+- [FileStore] keeps every entry in its own file. Files are sharded into directories by the first byte of the key
+  `root/ab/cdef...`. Entries are written to a temporary file and renamed. So an entry is never partially written
+
+## Example
+```rust
+use digestible::cas::{ContentStore, MemoryStore};
+use digestible::Digestible;
+use sha2::Sha256;
+#[derive(Digestible)]
+pub struct Build {
+    pub target: String,
+    pub flags: Vec<String>,
+}
+let store = MemoryStore::<Sha256>::new();
+let build = Build {
+    target: "x86_64".to_string(),
+    flags: vec!["--release".to_string()],
+};
+let key = store.put(&build).unwrap();
+assert_eq!(key, MemoryStore::<Sha256>::key_of(&build));
+assert!(store.contains(&key).unwrap());
+let bytes = store.get(&key).unwrap().unwrap();
+```
+*/
+use crate::format::{FormatV1, FormatWriter};
+use crate::Digestible;
+use byteorder::LittleEndian;
+use digest_0_10::Digest;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::fs::{self, File};
+use std::io::{ErrorKind, Write};
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::RwLock;
+
+/// The key of an entry. The hash of the stored bytes
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ContentKey(Vec<u8>);
+impl ContentKey {
+    /// Wraps the bytes of a hash
+    pub fn new(bytes: impl Into<Vec<u8>>) -> Self {
+        Self(bytes.into())
+    }
+    /// The bytes of the hash
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+    /// The lowercase hex of the hash
+    pub fn to_hex(&self) -> String {
+        self.to_string()
+    }
+}
+impl AsRef<[u8]> for ContentKey {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+/// Writes the lowercase hex of the hash
+impl Display for ContentKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.0.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+    }
+}
+/// Parses hex. Upper and lowercase are accepted
+impl FromStr for ContentKey {
+    type Err = CasError;
+    fn from_str(hex: &str) -> Result<Self, Self::Err> {
+        // from_str_radix also accepts a sign. So every byte is checked first
+        if hex.is_empty() || hex.len() % 2 != 0 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(CasError::InvalidKey(hex.to_string()));
+        }
+        (0..hex.len())
+            .step_by(2)
+            .map(|index| u8::from_str_radix(&hex[index..index + 2], 16))
+            .collect::<Result<Vec<u8>, _>>()
+            .map(Self)
+            .map_err(|_| CasError::InvalidKey(hex.to_string()))
+    }
+}
+
+/// An error returned by a [ContentStore]
+#[derive(Debug)]
+pub enum CasError {
+    /// Reading or writing the store failed
+    Io(std::io::Error),
+    /// The stored bytes do not match the key
+    Corrupted(ContentKey),
+    /// The key is not valid hex or has the wrong length
+    InvalidKey(String),
+}
+impl Display for CasError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CasError::Io(error) => write!(f, "content store io error: {error}"),
+            CasError::Corrupted(key) => write!(f, "content of {key} does not match its key"),
+            CasError::InvalidKey(key) => write!(f, "invalid content key {key:?}"),
+        }
+    }
+}
+impl std::error::Error for CasError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CasError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+impl From<std::io::Error> for CasError {
+    fn from(error: std::io::Error) -> Self {
+        CasError::Io(error)
+    }
+}
+
+/// Stores bytes under their hash. See [module docs](self)
+///
+/// Implementations only store and load bytes. Hashing and verifying are done by the provided methods
+pub trait ContentStore {
+    /// The hash used for the keys
+    type Digest: Digest;
+    /// Stores the bytes under the key without checking them.
+    ///
+    /// Storing a key that already exists does nothing.
+    /// Unless the stored bytes no longer match the key. Then they are replaced
+    fn put_raw(&self, key: &ContentKey, bytes: &[u8]) -> Result<(), CasError>;
+    /// Loads the bytes of the key without checking them
+    fn get_raw(&self, key: &ContentKey) -> Result<Option<Vec<u8>>, CasError>;
+    /// If the key is stored
+    fn contains(&self, key: &ContentKey) -> Result<bool, CasError>;
+    /// Removes the key. Returns false if it was not stored
+    fn remove(&self, key: &ContentKey) -> Result<bool, CasError>;
+
+    /// The key the value is stored under
+    fn key_of<T: Digestible + ?Sized>(value: &T) -> ContentKey
+    where
+        Self: Sized,
+    {
+        Self::key_of_bytes(&encode(value))
+    }
+    /// The key the bytes are stored under
+    fn key_of_bytes(bytes: &[u8]) -> ContentKey
+    where
+        Self: Sized,
+    {
+        ContentKey(Self::Digest::digest(bytes).to_vec())
+    }
+    /// Digests the value and stores the digested bytes. Returns the key
+    fn put<T: Digestible + ?Sized>(&self, value: &T) -> Result<ContentKey, CasError>
+    where
+        Self: Sized,
+    {
+        self.put_bytes(&encode(value))
+    }
+    /// Stores the bytes. Returns the key
+    fn put_bytes(&self, bytes: &[u8]) -> Result<ContentKey, CasError>
+    where
+        Self: Sized,
+    {
+        let key = Self::key_of_bytes(bytes);
+        self.put_raw(&key, bytes)?;
+        Ok(key)
+    }
+    /// Loads the bytes of the key.
+    ///
+    /// Returns [CasError::Corrupted] if the hash of the bytes is not the key
+    fn get(&self, key: &ContentKey) -> Result<Option<Vec<u8>>, CasError>
+    where
+        Self: Sized,
+    {
+        let Some(bytes) = self.get_raw(key)? else {
+            return Ok(None);
+        };
+        if Self::key_of_bytes(&bytes) != *key {
+            return Err(CasError::Corrupted(key.clone()));
+        }
+        Ok(Some(bytes))
+    }
+}
+
+/// The bytes stored by [ContentStore::put]
+fn encode<T: Digestible + ?Sized>(value: &T) -> Vec<u8> {
+    let mut writer = FormatWriter::<FormatV1, _>::new(Vec::new());
+    value.digest::<LittleEndian, _>(&mut writer);
+    writer.into_inner()
+}
+
+/// Keeps the entries in memory. See [module docs](self)
+pub struct MemoryStore<D> {
+    entries: RwLock<HashMap<ContentKey, Vec<u8>>>,
+    _digest: PhantomData<fn() -> D>,
+}
+impl<D: Digest> MemoryStore<D> {
+    /// An empty store
+    pub fn new() -> Self {
+        Self {
+            entries: RwLock::default(),
+            _digest: PhantomData,
+        }
+    }
+    /// The number of entries
+    pub fn len(&self) -> usize {
+        self.read().len()
+    }
+    /// If there are no entries
+    pub fn is_empty(&self) -> bool {
+        self.read().is_empty()
+    }
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, HashMap<ContentKey, Vec<u8>>> {
+        self.entries
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, HashMap<ContentKey, Vec<u8>>> {
+        self.entries
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+impl<D: Digest> Default for MemoryStore<D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<D: Digest> ContentStore for MemoryStore<D> {
+    type Digest = D;
+    fn put_raw(&self, key: &ContentKey, bytes: &[u8]) -> Result<(), CasError> {
+        let mut entries = self.write();
+        match entries.get_mut(key) {
+            Some(existing) if is_intact::<D>(key, existing) => {}
+            Some(existing) => *existing = bytes.to_vec(),
+            None => {
+                entries.insert(key.clone(), bytes.to_vec());
+            }
+        }
+        Ok(())
+    }
+    fn get_raw(&self, key: &ContentKey) -> Result<Option<Vec<u8>>, CasError> {
+        Ok(self.read().get(key).cloned())
+    }
+    fn contains(&self, key: &ContentKey) -> Result<bool, CasError> {
+        Ok(self.read().contains_key(key))
+    }
+    fn remove(&self, key: &ContentKey) -> Result<bool, CasError> {
+        Ok(self.write().remove(key).is_some())
+    }
+}
+
+/// Keeps every entry in its own file. See [module docs](self)
+pub struct FileStore<D> {
+    root: PathBuf,
+    _digest: PhantomData<fn() -> D>,
+}
+impl<D: Digest> FileStore<D> {
+    /// Uses the directory as the root of the store. The directory is created if it does not exist
+    pub fn open(root: impl Into<PathBuf>) -> Result<Self, CasError> {
+        let root = root.into();
+        fs::create_dir_all(&root)?;
+        Ok(Self {
+            root,
+            _digest: PhantomData,
+        })
+    }
+    /// The root directory
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+    /// The file of the key. `root/ab/cdef...`
+    pub fn path_of(&self, key: &ContentKey) -> Result<PathBuf, CasError> {
+        if key.as_bytes().len() != <D as Digest>::output_size() {
+            return Err(CasError::InvalidKey(key.to_hex()));
+        }
+        let hex = key.to_hex();
+        Ok(self.root.join(&hex[..2]).join(&hex[2..]))
+    }
+}
+impl<D: Digest> ContentStore for FileStore<D> {
+    type Digest = D;
+    fn put_raw(&self, key: &ContentKey, bytes: &[u8]) -> Result<(), CasError> {
+        let path = self.path_of(key)?;
+        match fs::read(&path) {
+            Ok(existing) if is_intact::<D>(key, &existing) => return Ok(()),
+            // A corrupted entry is replaced
+            Ok(_) => {}
+            Err(error) if error.kind() == ErrorKind::NotFound => {}
+            Err(error) => return Err(error.into()),
+        }
+        let shard = path.parent().expect("entries are inside of a shard");
+        fs::create_dir_all(shard)?;
+        let temp = shard.join(temp_name(key));
+        let result = File::create(&temp)
+            .and_then(|mut file| {
+                file.write_all(bytes)?;
+                file.sync_all()
+            })
+            .and_then(|_| fs::rename(&temp, &path));
+        if let Err(error) = result {
+            let _ = fs::remove_file(&temp);
+            return Err(error.into());
+        }
+        Ok(())
+    }
+    fn get_raw(&self, key: &ContentKey) -> Result<Option<Vec<u8>>, CasError> {
+        match fs::read(self.path_of(key)?) {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error.into()),
+        }
+    }
+    fn contains(&self, key: &ContentKey) -> Result<bool, CasError> {
+        Ok(self.path_of(key)?.try_exists()?)
+    }
+    fn remove(&self, key: &ContentKey) -> Result<bool, CasError> {
+        match fs::remove_file(self.path_of(key)?) {
+            Ok(()) => Ok(true),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(false),
+            Err(error) => Err(error.into()),
+        }
+    }
+}
+/// If the hash of the stored bytes is still the key
+fn is_intact<D: Digest>(key: &ContentKey, bytes: &[u8]) -> bool {
+    D::digest(bytes).as_slice() == key.as_bytes()
+}
+/// A name that is unique for this process. Starts with `.` so it is never a valid entry
+fn temp_name(key: &ContentKey) -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+    format!(".{}.{}.{count}.tmp", key, std::process::id())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CasError, ContentKey, ContentStore, FileStore, MemoryStore};
+    use crate::format::FormatV1;
+    use crate::Digester;
+    use byteorder::LittleEndian;
+    use sha2::{Digest, Sha256};
+
+    fn check_store(store: &impl ContentStore<Digest = Sha256>) {
+        let value = vec!["a".to_string(), "b".to_string()];
+        let key = store.put(&value).unwrap();
+        assert_eq!(
+            key.as_bytes(),
+            Sha256::new()
+                .digest_v::<FormatV1, LittleEndian>(&value)
+                .as_slice()
+        );
+        assert!(store.contains(&key).unwrap());
+        // Storing it again does nothing
+        assert_eq!(store.put(&value).unwrap(), key);
+
+        let bytes_key = store.put_bytes(b"hello").unwrap();
+        assert_eq!(store.get(&bytes_key).unwrap().unwrap(), b"hello");
+        assert_eq!(
+            bytes_key.as_bytes(),
+            <Sha256 as Digest>::digest(b"hello").as_slice()
+        );
+
+        assert!(store.remove(&key).unwrap());
+        assert!(!store.remove(&key).unwrap());
+        assert_eq!(store.get(&key).unwrap(), None);
+    }
+
+    #[test]
+    fn memory() {
+        let store = MemoryStore::<Sha256>::new();
+        check_store(&store);
+        assert_eq!(store.len(), 1);
+
+        let key = MemoryStore::<Sha256>::key_of_bytes(b"hello");
+        store.write().insert(key.clone(), b"jello".to_vec());
+        assert!(matches!(store.get(&key), Err(CasError::Corrupted(_))));
+        store.put_bytes(b"hello").unwrap();
+        assert_eq!(store.get(&key).unwrap().unwrap(), b"hello");
+    }
+
+    #[test]
+    fn file() {
+        let root = std::env::temp_dir().join(format!("digestible-cas-{}", std::process::id()));
+        let store = FileStore::<Sha256>::open(&root).unwrap();
+        check_store(&store);
+
+        let key = store.put_bytes(b"hello").unwrap();
+        let path = store.path_of(&key).unwrap();
+        let hex = key.to_hex();
+        assert!(path.ends_with(format!("{}/{}", &hex[..2], &hex[2..])));
+        // No temporary files are left
+        assert_eq!(
+            std::fs::read_dir(path.parent().unwrap()).unwrap().count(),
+            1
+        );
+
+        std::fs::write(&path, b"jello").unwrap();
+        assert!(matches!(store.get(&key), Err(CasError::Corrupted(_))));
+        // Putting it again repairs it
+        assert_eq!(store.put_bytes(b"hello").unwrap(), key);
+        assert_eq!(store.get(&key).unwrap().unwrap(), b"hello");
+        assert!(matches!(
+            store.path_of(&ContentKey::new([1, 2])),
+            Err(CasError::InvalidKey(_))
+        ));
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn hex() {
+        let key = ContentKey::new([0x00, 0xab, 0xff]);
+        assert_eq!(key.to_hex(), "00abff");
+        assert_eq!("00ABff".parse::<ContentKey>().unwrap(), key);
+        assert!("abc".parse::<ContentKey>().is_err());
+        assert!("zz".parse::<ContentKey>().is_err());
+        assert!("+a+b".parse::<ContentKey>().is_err());
+        assert!("-1".parse::<ContentKey>().is_err());
+    }
+}
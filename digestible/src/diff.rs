@@ -0,0 +1,288 @@
+/*!
+# Structural Diff
+
+Finds out why the digests of two values are different.
+
+[DigestTree] digests a value and stores a SHA-256 hash for every field, item and map value inside of it.
+The paths come from the hooks of [DigestWriter] such as [enter_field](DigestWriter::enter_field).
+The derive macro and the built in collections call them. So `PartialEq` or `Debug` are not needed.
+
+[diff] compares the trees of two values and returns the paths with different hashes.
+Only the deepest paths are returned. If `items[2].price` changed then `items[2]` and `items` are not returned.
+
+## Paths
+- The root is the empty path
+- Fields: `metadata`, `items[2].price`. Tuple struct fields use their index `point.0`
+- Sequences: `items[2]`
+- Maps and sets: `metadata["region"]`. Keys are written by their bytes.
+  Non empty printable strings are quoted. Keys up to 16 bytes are written as hex `[0x2a000000]`.
+  Longer keys are written as `#` followed by the first 8 bytes of their SHA-256 hash
+
+Values are digested with [FormatV1](crate::format::FormatV1) and LittleEndian.
+Types that do not call the hooks are one node. Their content is not split into paths.
+
+## Example
+```rust
+use digestible::diff::{diff, Difference, DifferenceKind};
+use digestible::Digestible;
+use std::collections::BTreeMap;
+#[derive(Digestible)]
+pub struct Item {
+    pub name: String,
+    pub price: u32,
+}
+#[derive(Digestible)]
+pub struct Order {
+    pub items: Vec<Item>,
+    pub metadata: BTreeMap<String, String>,
+}
+let item = |name: &str, price| Item {
+    name: name.to_string(),
+    price,
+};
+let before = Order {
+    items: vec![item("apple", 1), item("pear", 2), item("plum", 3)],
+    metadata: BTreeMap::from([("region".to_string(), "eu".to_string())]),
+};
+let after = Order {
+    items: vec![item("apple", 1), item("pear", 2), item("plum", 4)],
+    metadata: BTreeMap::from([("region".to_string(), "us".to_string())]),
+};
+assert_eq!(
+    diff(&before, &after),
+    vec![
+        Difference::new("items[2].price", DifferenceKind::Changed),
+        Difference::new("metadata[\"region\"]", DifferenceKind::Changed),
+    ]
+);
+```
+*/
+use crate::format::{Format, FormatV1, FormatWriter};
+use crate::sha256::Sha256Hasher;
+use crate::{DigestWriter, Digestible};
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use byteorder::LittleEndian;
+use core::fmt::{Display, Formatter, Write};
+
+/// The hash of every path of a value. See [module docs](self)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DigestTree {
+    nodes: BTreeMap<String, [u8; 32]>,
+}
+impl DigestTree {
+    /// Digests the value and records the hash of every path
+    pub fn new<T: Digestible + ?Sized>(value: &T) -> Self {
+        let mut writer = TraceWriter {
+            stack: Vec::new(),
+            nodes: BTreeMap::new(),
+        };
+        writer.push(String::new());
+        value.digest::<LittleEndian, _>(&mut writer);
+        while !writer.stack.is_empty() {
+            writer.pop();
+        }
+        Self {
+            nodes: writer.nodes,
+        }
+    }
+    /// The hash of the whole value
+    pub fn root(&self) -> &[u8; 32] {
+        self.nodes.get("").expect("the root is always recorded")
+    }
+    /// The hash of the path
+    pub fn get(&self, path: &str) -> Option<&[u8; 32]> {
+        self.nodes.get(path)
+    }
+    /// Every path and its hash. Sorted by the path
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[u8; 32])> {
+        self.nodes.iter().map(|(path, hash)| (path.as_str(), hash))
+    }
+    /// The deepest paths that are different between the trees. Sorted by the path
+    ///
+    /// Paths only in `other` are [Added](DifferenceKind::Added). Paths only in `self` are [Removed](DifferenceKind::Removed)
+    pub fn diff(&self, other: &Self) -> Vec<Difference> {
+        let mut differences: Vec<Difference> = Vec::new();
+        for (path, hash) in &self.nodes {
+            match other.nodes.get(path) {
+                Some(other_hash) if other_hash == hash => {}
+                Some(_) => differences.push(Difference::new(path, DifferenceKind::Changed)),
+                None => differences.push(Difference::new(path, DifferenceKind::Removed)),
+            }
+        }
+        for path in other.nodes.keys() {
+            if !self.nodes.contains_key(path) {
+                differences.push(Difference::new(path, DifferenceKind::Added));
+            }
+        }
+        // Added and removed paths hide their children. Changed paths are hidden by their children
+        let keep: Vec<bool> = differences
+            .iter()
+            .map(|difference| {
+                if difference.kind == DifferenceKind::Changed {
+                    !differences
+                        .iter()
+                        .any(|other| is_child(&other.path, &difference.path))
+                } else {
+                    !differences.iter().any(|other| {
+                        other.kind == difference.kind && is_child(&difference.path, &other.path)
+                    })
+                }
+            })
+            .collect();
+        let mut keep = keep.into_iter();
+        differences.retain(|_| keep.next().unwrap_or_default());
+        differences.sort_by(|a, b| a.path.cmp(&b.path));
+        differences
+    }
+}
+
+/// Digests both values and returns the deepest paths with different hashes. See [module docs](self)
+pub fn diff<T: Digestible + ?Sized>(a: &T, b: &T) -> Vec<Difference> {
+    DigestTree::new(a).diff(&DigestTree::new(b))
+}
+
+/// How a path is different
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DifferenceKind {
+    /// The path is in both values with a different hash
+    Changed,
+    /// The path is only in the second value
+    Added,
+    /// The path is only in the first value
+    Removed,
+}
+/// A path that is different between two values
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Difference {
+    /// The path. See [Paths](self#paths)
+    pub path: String,
+    /// How the path is different
+    pub kind: DifferenceKind,
+}
+impl Difference {
+    /// Creates a new difference
+    pub fn new(path: impl Into<String>, kind: DifferenceKind) -> Self {
+        Self {
+            path: path.into(),
+            kind,
+        }
+    }
+}
+/// Writes `changed items[2].price`. The root is written as `<root>`
+impl Display for Difference {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let kind = match self.kind {
+            DifferenceKind::Changed => "changed",
+            DifferenceKind::Added => "added",
+            DifferenceKind::Removed => "removed",
+        };
+        if self.path.is_empty() {
+            write!(f, "{kind} <root>")
+        } else {
+            write!(f, "{kind} {}", self.path)
+        }
+    }
+}
+
+/// If `child` is inside of `parent`
+fn is_child(child: &str, parent: &str) -> bool {
+    if parent.is_empty() {
+        return !child.is_empty();
+    }
+    child
+        .strip_prefix(parent)
+        .is_some_and(|rest| rest.starts_with('.') || rest.starts_with('['))
+}
+
+/// Hashes the bytes written between every `enter_*` hook and its `exit`
+struct TraceWriter {
+    /// The open paths. The root is first
+    stack: Vec<Frame>,
+    nodes: BTreeMap<String, [u8; 32]>,
+}
+struct Frame {
+    path: String,
+    hasher: Sha256Hasher,
+}
+impl TraceWriter {
+    fn parent(&self) -> &str {
+        self.stack
+            .last()
+            .map(|frame| frame.path.as_str())
+            .unwrap_or_default()
+    }
+    fn push(&mut self, path: String) {
+        self.stack.push(Frame {
+            path,
+            hasher: Sha256Hasher::new(),
+        });
+    }
+    fn pop(&mut self) {
+        if let Some(frame) = self.stack.pop() {
+            self.nodes.insert(frame.path, frame.hasher.finalize());
+        }
+    }
+}
+impl DigestWriter for TraceWriter {
+    const FORMAT: Format = Format::V1;
+    const TRACES_PATHS: bool = true;
+    fn write(&mut self, data: &[u8]) {
+        for frame in &mut self.stack {
+            frame.hasher.update(data);
+        }
+    }
+    fn enter_field(&mut self, name: &str) {
+        let parent = self.parent();
+        let path = if parent.is_empty() {
+            String::from(name)
+        } else {
+            alloc::format!("{parent}.{name}")
+        };
+        self.push(path);
+    }
+    fn enter_index(&mut self, index: usize) {
+        let path = alloc::format!("{}[{index}]", self.parent());
+        self.push(path);
+    }
+    fn enter_key<K: Digestible + ?Sized>(&mut self, key: &K) {
+        let mut path = String::from(self.parent());
+        path.push('[');
+        write_key(&mut path, key);
+        path.push(']');
+        self.push(path);
+    }
+    fn exit(&mut self) {
+        // The root is only removed at the end
+        if self.stack.len() > 1 {
+            self.pop();
+        }
+    }
+}
+/// Writes the key by its bytes. See [Paths](self#paths)
+fn write_key<K: Digestible + ?Sized>(path: &mut String, key: &K) {
+    let mut bytes = FormatWriter::<FormatV1, _>::new(Vec::new());
+    key.digest::<LittleEndian, _>(&mut bytes);
+    let bytes = bytes.into_inner();
+    // Strings are written with a u64 length in V1
+    let text = bytes
+        .get(8..)
+        .filter(|text| !text.is_empty() && bytes[..8] == (text.len() as u64).to_le_bytes())
+        .and_then(|text| core::str::from_utf8(text).ok())
+        .filter(|text| !text.chars().any(char::is_control));
+    let _ = if let Some(text) = text {
+        write!(path, "{text:?}")
+    } else if bytes.len() <= 16 {
+        path.push_str("0x");
+        bytes.iter().try_for_each(|byte| write!(path, "{byte:02x}"))
+    } else {
+        path.push('#');
+        let mut hasher = Sha256Hasher::new();
+        hasher.update(&bytes);
+        let hash = hasher.finalize();
+        hash[..8]
+            .iter()
+            .try_for_each(|byte| write!(path, "{byte:02x}"))
+    };
+}
@@ -0,0 +1,43 @@
+/*!
+# Digesting chrono Types
+
+## Example
+
+```rust
+use chrono::{DateTime, FixedOffset, Utc};
+use digestible::{Digestible, Digester};
+use sha2::{Digest, Sha256};
+#[derive(Digestible)]
+pub struct Event {
+    #[digestible(digest_with = chrono::digest_normalized)]
+    pub created: DateTime<Utc>,
+    #[digestible(digest_with = chrono::digest_normalized)]
+    pub local: DateTime<FixedOffset>,
+}
+
+let now = Utc::now();
+let event = Event {
+    created: now,
+    local: now.fixed_offset(),
+};
+let mut hasher = sha2::Sha256::new();
+let result = hasher.digest_native(&event);
+assert_eq!(result.len(), 32);
+```
+*/
+use crate::DigestWriter;
+use ::chrono::{DateTime, TimeZone};
+use byteorder::ByteOrder;
+
+/// Writes the instant without the offset.
+///
+/// The same instant in different time zones has the same digest.
+/// Writes the UTC seconds since the unix epoch as an i64 and the nanoseconds as a u32
+#[inline]
+pub fn digest_normalized<B: ByteOrder, W: DigestWriter>(
+    date_time: &DateTime<impl TimeZone>,
+    writer: &mut W,
+) {
+    writer.write_i64::<B>(date_time.timestamp());
+    writer.write_u32::<B>(date_time.timestamp_subsec_nanos());
+}
@@ -0,0 +1,422 @@
+/*!
+# Digesting JSON
+
+The default [Digestible] implementation of [Value] writes values without any framing.
+So different documents can have the same digest.
+This module provides two unambiguous encodings.
+
+## Canonical JSON
+[digest_jcs] and [Jcs] write the [RFC 8785](https://www.rfc-editor.org/rfc/rfc8785)
+JSON Canonicalization Scheme serialization of the value.
+
+- Object keys are sorted by their UTF-16 code units.
+- Numbers are formatted the same as JavaScript's `Number.prototype.toString`.
+  Every number is treated as a double. So `1` and `1.0` are the same.
+- Strings are escaped the same as JavaScript's `JSON.stringify`.
+- No whitespace is written.
+
+The digest is the same as hashing the output of the `canonicalize` npm package
+or any other JCS implementation.
+
+Enable the `float_roundtrip` feature of serde_json when parsing documents.
+Otherwise some floats are parsed one bit off from what JavaScript parses.
+
+## Tagged
+[digest_tagged] and [Tagged] write every value with a u8 tag and length prefix.
+
+| Value   | Tag | Followed By                                                     |
+|---------|-----|-----------------------------------------------------------------|
+| Null    | `0` |                                                                 |
+| Bool    | `1` | `0` or `1` as a u8                                              |
+| Integer | `2` | i64                                                             |
+| Integer | `3` | u64. Only used for integers larger than [i64::MAX]              |
+| Float   | `4` | f64                                                             |
+| String  | `5` | The length in bytes as a u64 then the UTF-8 bytes               |
+| Array   | `6` | The length as a u64 then each value                             |
+| Object  | `7` | The length as a u64 then each key as a String and its value     |
+| Error   | `8` | A number that is not a finite f64. Written the same as a String |
+
+Floats without a fractional part that fit in an i64 or u64 are written as integers.
+So `1` and `1.0` are the same. Object keys are sorted by their bytes.
+
+Numbers that are not a finite f64 can only be parsed with the `arbitrary_precision` feature of serde_json.
+Such as `1e400`. They are written with the error tag followed by the number as text.
+
+## Example
+```rust
+use digestible::digest_with::json::Jcs;
+use digestible::Digester;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+
+let value = json!({"b": 1.0, "a": [true, null]});
+let mut canonical = Vec::new();
+digestible::Digestible::digest_native(&Jcs(&value), &mut canonical);
+assert_eq!(canonical, br#"{"a":[true,null],"b":1}"#);
+
+let digest = Sha256::new().digest_native(&Jcs(&value));
+let expected = <Sha256 as Digest>::digest(br#"{"a":[true,null],"b":1}"#);
+assert_eq!(digest.as_slice(), expected.as_slice());
+```
+*/
+use crate::{DigestWriter, Digestible};
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use byteorder::ByteOrder;
+use core::fmt::Write;
+use serde_json::{Number, Value};
+
+/// Writes the RFC 8785 canonical JSON of the value
+///
+/// # Panics
+/// If a number is not a finite f64. JCS can not represent it.
+/// Only possible with the `arbitrary_precision` feature of serde_json
+pub fn digest_jcs<B: ByteOrder, W: DigestWriter>(value: &Value, writer: &mut W) {
+    match value {
+        Value::Null => writer.write(b"null"),
+        Value::Bool(true) => writer.write(b"true"),
+        Value::Bool(false) => writer.write(b"false"),
+        Value::Number(number) => write_jcs_number(number, writer),
+        Value::String(string) => write_jcs_string(string, writer),
+        Value::Array(array) => {
+            writer.write(b"[");
+            for (index, value) in array.iter().enumerate() {
+                if index != 0 {
+                    writer.write(b",");
+                }
+                digest_jcs::<B, W>(value, writer);
+            }
+            writer.write(b"]");
+        }
+        Value::Object(object) => {
+            let mut entries: Vec<_> = object.iter().collect();
+            entries.sort_unstable_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+            writer.write(b"{");
+            for (index, (key, value)) in entries.into_iter().enumerate() {
+                if index != 0 {
+                    writer.write(b",");
+                }
+                write_jcs_string(key, writer);
+                writer.write(b":");
+                digest_jcs::<B, W>(value, writer);
+            }
+            writer.write(b"}");
+        }
+    }
+}
+/// Writes the value with a tag and length prefix. See [module docs](self)
+pub fn digest_tagged<B: ByteOrder, W: DigestWriter>(value: &Value, writer: &mut W) {
+    match value {
+        Value::Null => writer.write_u8(0),
+        Value::Bool(bool) => {
+            writer.write_u8(1);
+            writer.write_bool(*bool);
+        }
+        Value::Number(number) => write_tagged_number::<B, W>(number, writer),
+        Value::String(string) => write_tagged_string::<B, W>(string, writer),
+        Value::Array(array) => {
+            writer.write_u8(6);
+            writer.write_u64::<B>(array.len() as u64);
+            for value in array {
+                digest_tagged::<B, W>(value, writer);
+            }
+        }
+        Value::Object(object) => {
+            writer.write_u8(7);
+            writer.write_u64::<B>(object.len() as u64);
+            let mut entries: Vec<_> = object.iter().collect();
+            entries.sort_unstable_by_key(|(key, _)| *key);
+            for (key, value) in entries {
+                write_tagged_string::<B, W>(key, writer);
+                digest_tagged::<B, W>(value, writer);
+            }
+        }
+    }
+}
+/// Digests the value as RFC 8785 canonical JSON. See [digest_jcs]
+#[derive(Debug, Clone, Copy)]
+pub struct Jcs<'a>(pub &'a Value);
+impl Digestible for Jcs<'_> {
+    fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
+        digest_jcs::<B, W>(self.0, writer)
+    }
+}
+/// Digests the value with tags and length prefixes. See [digest_tagged]
+#[derive(Debug, Clone, Copy)]
+pub struct Tagged<'a>(pub &'a Value);
+impl Digestible for Tagged<'_> {
+    fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
+        digest_tagged::<B, W>(self.0, writer)
+    }
+}
+
+fn write_tagged_string<B: ByteOrder, W: DigestWriter>(string: &str, writer: &mut W) {
+    writer.write_u8(5);
+    write_tagged_text::<B, W>(string, writer);
+}
+fn write_tagged_text<B: ByteOrder, W: DigestWriter>(string: &str, writer: &mut W) {
+    writer.write_u64::<B>(string.len() as u64);
+    writer.write(string.as_bytes());
+}
+fn write_tagged_number<B: ByteOrder, W: DigestWriter>(number: &Number, writer: &mut W) {
+    if let Some(int) = number.as_i64() {
+        writer.write_u8(2);
+        writer.write_i64::<B>(int);
+    } else if let Some(int) = number.as_u64() {
+        writer.write_u8(3);
+        writer.write_u64::<B>(int);
+    } else if let Some(float) = number.as_f64() {
+        // 2^63 and 2^64 are the first floats that do not fit. Casts would saturate them to the max integer
+        const I64_END: f64 = 9_223_372_036_854_775_808.0;
+        const U64_END: f64 = 18_446_744_073_709_551_616.0;
+        if float.fract() == 0.0 && (-I64_END..I64_END).contains(&float) {
+            writer.write_u8(2);
+            writer.write_i64::<B>(float as i64);
+        } else if float.fract() == 0.0 && float > 0.0 && float < U64_END {
+            writer.write_u8(3);
+            writer.write_u64::<B>(float as u64);
+        } else {
+            writer.write_u8(4);
+            writer.write_f64::<B>(float);
+        }
+    } else {
+        writer.write_u8(8);
+        write_tagged_text::<B, W>(&number.to_string(), writer);
+    }
+}
+/// Escapes the string the same as `JSON.stringify`
+fn write_jcs_string<W: DigestWriter>(string: &str, writer: &mut W) {
+    writer.write(b"\"");
+    let bytes = string.as_bytes();
+    let mut start = 0;
+    for (index, byte) in bytes.iter().enumerate() {
+        let escape: &[u8] = match byte {
+            b'"' => b"\\\"",
+            b'\\' => b"\\\\",
+            b'\x08' => b"\\b",
+            b'\x0C' => b"\\f",
+            b'\n' => b"\\n",
+            b'\r' => b"\\r",
+            b'\t' => b"\\t",
+            0..=0x1F => b"",
+            _ => continue,
+        };
+        writer.write(&bytes[start..index]);
+        if escape.is_empty() {
+            const HEX: &[u8; 16] = b"0123456789abcdef";
+            writer.write(&[
+                b'\\',
+                b'u',
+                b'0',
+                b'0',
+                HEX[(byte >> 4) as usize],
+                HEX[(byte & 0xF) as usize],
+            ]);
+        } else {
+            writer.write(escape);
+        }
+        start = index + 1;
+    }
+    writer.write(&bytes[start..]);
+    writer.write(b"\"");
+}
+/// Formats the number the same as `Number.prototype.toString`
+fn write_jcs_number<W: DigestWriter>(number: &Number, writer: &mut W) {
+    let Some(value) = number.as_f64() else {
+        panic!("JCS can not represent the number {number}. It is not a finite f64");
+    };
+    if value == 0.0 {
+        // Includes -0
+        writer.write(b"0");
+        return;
+    }
+    if value.is_sign_negative() {
+        writer.write(b"-");
+    }
+    // The Rust exponent format is the shortest representation that round trips.
+    // Same as the digits required by ECMAScript
+    let mut buffer = StackBuffer::default();
+    write!(buffer, "{:e}", value.abs()).expect("f64 fits in the buffer");
+    let formatted = buffer.as_bytes();
+    let exponent_index = formatted
+        .iter()
+        .position(|v| *v == b'e')
+        .expect("exponent format always contains e");
+    let mut digits = StackBuffer::default();
+    for digit in formatted[..exponent_index].iter().filter(|v| **v != b'.') {
+        digits.push(*digit);
+    }
+    let digits = digits.as_bytes();
+    let exponent: i32 = core::str::from_utf8(&formatted[exponent_index + 1..])
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .expect("exponent is a valid integer");
+    let k = digits.len() as i32;
+    // The position of the decimal point relative to the digits
+    let n = exponent + 1;
+    if k <= n && n <= 21 {
+        writer.write(digits);
+        for _ in 0..n - k {
+            writer.write(b"0");
+        }
+    } else if 0 < n && n <= 21 {
+        writer.write(&digits[..n as usize]);
+        writer.write(b".");
+        writer.write(&digits[n as usize..]);
+    } else if -6 < n && n <= 0 {
+        writer.write(b"0.");
+        for _ in 0..-n {
+            writer.write(b"0");
+        }
+        writer.write(digits);
+    } else {
+        writer.write(&digits[..1]);
+        if k > 1 {
+            writer.write(b".");
+            writer.write(&digits[1..]);
+        }
+        let mut exponent_buffer = StackBuffer::default();
+        write!(exponent_buffer, "e{:+}", n - 1).expect("exponent fits in the buffer");
+        writer.write(exponent_buffer.as_bytes());
+    }
+}
+/// Big enough for any f64 in exponent format
+#[derive(Default)]
+struct StackBuffer {
+    buffer: [u8; 32],
+    len: usize,
+}
+impl StackBuffer {
+    fn push(&mut self, byte: u8) {
+        self.buffer[self.len] = byte;
+        self.len += 1;
+    }
+    fn as_bytes(&self) -> &[u8] {
+        &self.buffer[..self.len]
+    }
+}
+impl Write for StackBuffer {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        let end = self.len + s.len();
+        if end > self.buffer.len() {
+            return Err(core::fmt::Error);
+        }
+        self.buffer[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{digest_jcs, digest_tagged};
+    use byteorder::BigEndian;
+    use serde_json::{json, Value};
+
+    fn jcs(value: &Value) -> String {
+        let mut digest = Vec::new();
+        digest_jcs::<BigEndian, _>(value, &mut digest);
+        String::from_utf8(digest).unwrap()
+    }
+    fn tagged(value: &Value) -> Vec<u8> {
+        let mut digest = Vec::new();
+        digest_tagged::<BigEndian, _>(value, &mut digest);
+        digest
+    }
+
+    #[test]
+    fn jcs_rfc_example() {
+        let value: Value = serde_json::from_str(
+            r#"{
+                "numbers": [333333333.33333329, 1E30, 4.50, 2e-3, 0.000000000000000000000000001],
+                "string": "\u20ac$\u000F\u000aA'\u0042\u0022\u005c\\\"\/",
+                "literals": [null, true, false]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            jcs(&value),
+            r#"{"literals":[null,true,false],"numbers":[333333333.3333333,1e+30,4.5,0.002,1e-27],"string":"€$\u000f\nA'B\"\\\\\"/"}"#
+        );
+    }
+
+    #[test]
+    fn jcs_key_order() {
+        let value: Value = serde_json::from_str(
+            r#"{"\u20ac": 1, "\r": 2, "\ufb33": 3, "1": 4, "\ud83d\ude00": 5, "\u0080": 6, "\u00f6": 7}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            jcs(&value),
+            "{\"\\r\":2,\"1\":4,\"\u{80}\":6,\"ö\":7,\"€\":1,\"😀\":5,\"\u{fb33}\":3}"
+        );
+    }
+
+    #[test]
+    fn jcs_numbers() {
+        let cases = [
+            (json!(0), "0"),
+            (json!(-0.0), "0"),
+            (json!(1.0), "1"),
+            (json!(-1.5), "-1.5"),
+            (json!(1e21), "1e+21"),
+            (json!(1e20), "100000000000000000000"),
+            (json!(1e-7), "1e-7"),
+            (json!(0.000001), "0.000001"),
+            (json!(123e20), "1.23e+22"),
+            (json!(5e-324), "5e-324"),
+            (json!(1.7976931348623157e308), "1.7976931348623157e+308"),
+            (json!(9007199254740992u64), "9007199254740992"),
+            (json!(u64::MAX), "18446744073709552000"),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(jcs(&value), expected);
+        }
+    }
+
+    #[test]
+    fn tagged_integer_bounds() {
+        let tag = |value: Value| tagged(&value)[0];
+        assert_eq!(
+            tagged(&json!(-9223372036854775808.0)),
+            tagged(&json!(i64::MIN))
+        );
+        assert_eq!(tag(json!(9223372036854774784.0)), 2);
+        // 2^63 does not fit in an i64
+        assert_eq!(tag(json!(9223372036854775808.0)), 3);
+        assert_ne!(
+            tagged(&json!(9223372036854775808.0)),
+            tagged(&json!(i64::MAX))
+        );
+        assert_eq!(
+            tagged(&json!(9223372036854775808.0)),
+            tagged(&json!(9223372036854775808u64))
+        );
+        // 2^64 does not fit in a u64
+        assert_eq!(tag(json!(18446744073709551616.0)), 4);
+        assert_ne!(
+            tagged(&json!(18446744073709551616.0)),
+            tagged(&json!(u64::MAX))
+        );
+        assert_eq!(tag(json!(-9223372036854777856.0)), 4);
+    }
+
+    #[test]
+    fn tagged_framing() {
+        assert_eq!(tagged(&json!(1)), tagged(&json!(1.0)));
+        assert_ne!(tagged(&json!("")), tagged(&json!(null)));
+        assert_ne!(tagged(&json!([[1], []])), tagged(&json!([[], [1]])));
+        assert_eq!(
+            tagged(&json!({"a": null})),
+            [
+                &[7][..],
+                &1u64.to_be_bytes(),
+                &[5],
+                &1u64.to_be_bytes(),
+                b"a",
+                &[0]
+            ]
+            .concat()
+        );
+    }
+}
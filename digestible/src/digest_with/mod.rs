@@ -5,9 +5,17 @@
 
 */
 pub mod atomics;
+#[cfg(feature = "chrono")]
+pub mod chrono;
 pub mod floats;
+#[cfg(feature = "serde_json")]
+pub mod json;
+pub mod raw_bytes;
+#[cfg(feature = "time")]
+pub mod time;
 
 use crate::hash_digester::HashableHack;
+use crate::multiset::{MultisetDigest, UnorderedCollection};
 use crate::DigestWriter;
 use byteorder::ByteOrder;
 use core::hash::Hash;
@@ -39,6 +47,32 @@ pub fn digest_as_bytes<B: ByteOrder, W: DigestWriter>(bytes: impl AsRef<[u8]>, w
     writer.write(bytes.as_ref());
 }
 
+/// Digests the items of the collection without depending on their order.
+///
+/// Written the same as a [MultisetDigest] of the items inserted with [insert_v](MultisetDigest::insert_v) and the format of the writer.
+/// No sorting or allocation is needed. [Read More](crate::multiset)
+///
+/// # Example
+/// ```
+/// use digestible::Digestible;
+/// use std::collections::HashSet;
+/// #[derive(Digestible)]
+/// pub struct Tags {
+///     #[digestible(digest_with = unordered)]
+///     pub tags: HashSet<String>,
+/// }
+/// ```
+pub fn unordered<B: ByteOrder, W: DigestWriter>(
+    collection: &(impl UnorderedCollection + ?Sized),
+    writer: &mut W,
+) {
+    let mut multiset = MultisetDigest::new();
+    for item in collection.unordered_items() {
+        multiset.insert_as::<B, W>(&item, writer.digest_key());
+    }
+    crate::Digestible::digest::<B, W>(&multiset, writer);
+}
+
 /// Takes a type that implements [Hash](core::hash::Hash) and writes it to the given writer.
 /// This is useful for types that do not implement [Digestible](crate::Digestible).
 ///
@@ -61,7 +95,7 @@ pub fn digest_as_bytes<B: ByteOrder, W: DigestWriter>(bytes: impl AsRef<[u8]>, w
 /// assert_eq!(result.len(), 32);
 /// ```
 pub fn digest_with_hash<B: ByteOrder, W: DigestWriter>(hash: &impl Hash, writer: &mut W) {
-    let mut hashable_hack = HashableHack(writer);
+    let mut hashable_hack = HashableHack::<_, B>::new(writer);
     hash.hash(&mut hashable_hack);
 }
 
@@ -90,3 +124,82 @@ pub fn digest_with_hash<B: ByteOrder, W: DigestWriter>(hash: &impl Hash, writer:
 pub fn digest_as_str_ref<B: ByteOrder, W: DigestWriter>(hash: &impl AsRef<str>, writer: &mut W) {
     writer.write_str(hash.as_ref());
 }
+
+/// Digests the value with its [Serialize](serde::Serialize) implementation.
+/// See [serde_digest](crate::serde_digest) for the encoding
+///
+/// # Panics
+/// If the Serialize implementation returns an error. Use [try_digest_serialize] to handle the error
+/// # Example
+/// ```
+/// use digestible::Digestible;
+/// use serde::Serialize;
+/// #[derive(Serialize, Default)]
+/// pub struct ThirdParty(pub u32);
+/// #[derive(Digestible, Default)]
+/// pub struct MyStruct {
+///     #[digestible(digest_with = digest_serialize)]
+///     pub third_party: ThirdParty,
+/// }
+/// ```
+#[cfg(feature = "serde")]
+pub fn digest_serialize<B: ByteOrder, W: DigestWriter>(
+    value: &impl serde::Serialize,
+    writer: &mut W,
+) {
+    if let Err(error) = try_digest_serialize::<B, W>(value, writer) {
+        panic!("Failed to digest value: {error}");
+    }
+}
+/// Same as [digest_serialize] but also writes the names of structs, fields and variants
+///
+/// # Panics
+/// If the Serialize implementation returns an error
+#[cfg(feature = "serde")]
+pub fn digest_serialize_named<B: ByteOrder, W: DigestWriter>(
+    value: &impl serde::Serialize,
+    writer: &mut W,
+) {
+    let mut serializer =
+        crate::serde_digest::DigestSerializer::<W, B>::new(writer).with_names(true);
+    if let Err(error) = value.serialize(&mut serializer) {
+        panic!("Failed to digest value: {error}");
+    }
+}
+/// Same as [digest_serialize] but returns the error of the Serialize implementation
+#[cfg(feature = "serde")]
+pub fn try_digest_serialize<B: ByteOrder, W: DigestWriter>(
+    value: &impl serde::Serialize,
+    writer: &mut W,
+) -> Result<(), crate::serde_digest::Error> {
+    value.serialize(&mut crate::serde_digest::DigestSerializer::<W, B>::new(
+        writer,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::digest_with_hash;
+    use byteorder::{BigEndian, LittleEndian};
+    use core::time::Duration;
+
+    #[test]
+    fn hash_uses_byte_order() {
+        let mut little = Vec::new();
+        digest_with_hash::<LittleEndian, _>(&0x0102u16, &mut little);
+        assert_eq!(little, vec![2, 1]);
+        let mut big = Vec::new();
+        digest_with_hash::<BigEndian, _>(&0x0102u16, &mut big);
+        assert_eq!(big, vec![1, 2]);
+    }
+
+    #[test]
+    fn duration() {
+        use crate::Digestible;
+        let mut digest = Vec::new();
+        Duration::new(1, 2).digest::<BigEndian, _>(&mut digest);
+        let mut expected = 1u64.to_be_bytes().to_vec();
+        expected.extend_from_slice(&2u32.to_be_bytes());
+        assert_eq!(digest, expected);
+    }
+}
@@ -0,0 +1,97 @@
+/*!
+# Digesting the Raw Bytes of a Type
+
+For plain old data types. Such as `#[repr(C)]` structs and unions used with FFI.
+
+The bytes are written in the layout of the current platform. So the [ByteOrder] is ignored.
+
+## Example
+
+```rust
+use digestible::{Digestible, Digester};
+use sha2::{Digest, Sha256};
+#[derive(Digestible, Clone, Copy, Default)]
+#[digestible(bytes)]
+#[repr(C)]
+pub struct Header {
+    pub magic: [u8; 4],
+    pub version: u32,
+}
+
+#[derive(Digestible, Default)]
+pub struct MyStruct {
+    pub header: Header,
+    #[digestible(digest_with = raw_bytes::digest_raw_bytes)]
+    pub checksum: [u32; 4],
+}
+
+let mut hasher = sha2::Sha256::new();
+let result = hasher.digest_native(&MyStruct::default());
+assert_eq!(result.len(), 32);
+```
+*/
+use crate::DigestWriter;
+use byteorder::ByteOrder;
+
+/// A type that has every byte of its representation initialized.
+///
+/// Implemented by `#[digestible(bytes)]`.
+/// The derive checks that every field implements NoPadding and that there is no padding between them.
+///
+/// # Safety
+/// The type must not contain any padding bytes, pointers or other uninitialized memory.
+pub unsafe trait NoPadding: Copy + 'static {}
+
+macro_rules! no_padding {
+    ($($ty:ty),*) => {
+        $(
+            unsafe impl NoPadding for $ty {}
+        )*
+    };
+}
+no_padding!(
+    (),
+    bool,
+    char,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64
+);
+unsafe impl<T: NoPadding, const N: usize> NoPadding for [T; N] {}
+
+/// Writes the in memory representation of the value.
+#[inline(always)]
+pub fn digest_raw_bytes<B: ByteOrder, W: DigestWriter>(value: &impl NoPadding, writer: &mut W) {
+    writer.write(as_bytes(value));
+}
+#[inline(always)]
+fn as_bytes<T: NoPadding>(value: &T) -> &[u8] {
+    // Safety: NoPadding guarantees every byte of T is initialized
+    unsafe {
+        core::slice::from_raw_parts(value as *const T as *const u8, core::mem::size_of::<T>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::digest_raw_bytes;
+    use byteorder::LittleEndian;
+
+    #[test]
+    fn array() {
+        let mut digest = Vec::new();
+        digest_raw_bytes::<LittleEndian, _>(&[1u16, 2u16], &mut digest);
+        assert_eq!(digest, [1u16.to_ne_bytes(), 2u16.to_ne_bytes()].concat());
+    }
+}
@@ -0,0 +1,39 @@
+/*!
+# Digesting time Types
+
+## Example
+
+```rust
+use digestible::{Digestible, Digester};
+use sha2::{Digest, Sha256};
+use time::OffsetDateTime;
+#[derive(Digestible)]
+pub struct Event {
+    #[digestible(digest_with = time::digest_normalized)]
+    pub created: OffsetDateTime,
+}
+
+let event = Event {
+    created: OffsetDateTime::UNIX_EPOCH,
+};
+let mut hasher = sha2::Sha256::new();
+let result = hasher.digest_native(&event);
+assert_eq!(result.len(), 32);
+```
+*/
+use crate::DigestWriter;
+use ::time::OffsetDateTime;
+use byteorder::ByteOrder;
+
+/// Writes the instant without the offset.
+///
+/// The same instant with different offsets has the same digest.
+/// Same as `digest_with::chrono::digest_normalized` of the `chrono` feature
+#[inline]
+pub fn digest_normalized<B: ByteOrder, W: DigestWriter>(
+    date_time: &OffsetDateTime,
+    writer: &mut W,
+) {
+    writer.write_i64::<B>(date_time.unix_timestamp());
+    writer.write_u32::<B>(date_time.nanosecond());
+}
@@ -1,6 +1,9 @@
 use crate::digestible::Digestible;
+use crate::format::{EncodingFormat, WithFormat};
+use crate::keyed::WithKey;
 
 use byteorder::ByteOrder;
+use core::marker::PhantomData;
 /// A Type that can Digest data into a Target.
 pub trait Digester {
     type Target;
@@ -41,6 +44,30 @@ pub trait Digester {
     /// let result = hasher.digest::<NativeEndian>(&test);
     /// ```
     fn digest<B: ByteOrder>(self, data: &impl Digestible) -> Self::Target;
+    /// Digest the Data using the encoding format `F`. See [format](crate::format)
+    ///
+    /// ## Example
+    /// ```rust
+    /// use byteorder::LittleEndian;
+    /// use digestible::format::FormatV1;
+    /// use digestible::Digester;
+    /// use sha2::{Digest, Sha256};
+    ///
+    /// let result = Sha256::new().digest_v::<FormatV1, LittleEndian>(&"Hello");
+    /// ```
+    fn digest_v<F: EncodingFormat, B: ByteOrder>(self, data: &impl Digestible) -> Self::Target
+    where
+        Self: Sized,
+    {
+        self.digest::<B>(&WithFormat::<F, _>(data, PhantomData))
+    }
+    /// Digest the Data with the secret key of `salted` and `keyed` fields. See [keyed](crate::keyed)
+    fn digest_keyed<B: ByteOrder>(self, key: &[u8], data: &impl Digestible) -> Self::Target
+    where
+        Self: Sized,
+    {
+        self.digest::<B>(&WithKey { key, value: data })
+    }
     /// Calls [digest](Self::digest) with [NativeEndian](byteorder::NativeEndian) as the ByteOrder
     fn digest_native(self, data: &impl Digestible) -> Self::Target
     where
@@ -68,7 +95,7 @@ pub trait Digester {
 ///
 /// Giving you access to use [sha2](https://crates.io/crates/sha2), [sha1](https://crates.io/crates/sha1), [md-5](https://crates.io/crates/md-5) and more
 #[cfg(feature = "digest_0_10")]
-mod digest_0_10 {
+pub(crate) mod digest_0_10 {
     use crate::digester::Digester;
     use crate::digestible::Digestible;
     use byteorder::ByteOrder;
@@ -80,7 +107,8 @@ mod digest_0_10 {
             self.finalize()
         }
     }
-    struct DigestConsumerInner<'digest, T: Digest>(&'digest mut T);
+    /// Writes into a [Digest]
+    pub(crate) struct DigestConsumerInner<'digest, T: Digest>(pub(crate) &'digest mut T);
 
     impl<D: Digest> crate::DigestWriter for DigestConsumerInner<'_, D> {
         /// Calls [Digest::update](digest::Digest::update) on the digest with the given data.
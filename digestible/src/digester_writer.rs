@@ -1,3 +1,5 @@
+use crate::format::Format;
+use crate::Digestible;
 use byteorder::ByteOrder;
 macro_rules! write_doc {
     ($num:ty, $endian_write:ident) => {
@@ -72,6 +74,20 @@ macro_rules! deref_and_call_inner {
         deref_and_call_inner!(write_i128,ByteOrder, data: i128);
         deref_and_call_inner!(write_f32,ByteOrder, data: f32);
         deref_and_call_inner!(write_f64,ByteOrder, data: f64);
+        deref_and_call_inner!(enter_field, name: &str);
+        deref_and_call_inner!(enter_index, index: usize);
+        #[inline(always)]
+        fn enter_key<K: $crate::Digestible + ?Sized>(&mut self, key: &K) {
+            (**self).enter_key(key)
+        }
+        #[inline(always)]
+        fn exit(&mut self) {
+            (**self).exit()
+        }
+        #[inline(always)]
+        fn digest_key(&self) -> Option<&[u8]> {
+            (**self).digest_key()
+        }
 
     };
 }
@@ -83,10 +99,64 @@ macro_rules! deref_and_call_inner {
 /// ## Default Implementations
 /// - [`Vec<u8>`] requires 'alloc'
 /// - [bytes::BytesMut] (requires the `bytes` feature)
+///
+/// ## Wrapping Writers
+/// A writer that wraps another writer must forward more than [write](DigestWriter::write).
+/// The defaults of these items do not ask the inner writer. So a wrapper that does not forward them
+/// silently digests a [FormatV1](crate::format::FormatV1) value as Legacy, drops the key of [keyed](crate::keyed) fields
+/// and hides the paths from [diff](crate::diff).
+/// - [FORMAT](DigestWriter::FORMAT)
+/// - [TRACES_PATHS](DigestWriter::TRACES_PATHS)
+/// - [digest_key](DigestWriter::digest_key)
+/// - [enter_field](DigestWriter::enter_field), [enter_index](DigestWriter::enter_index),
+///   [enter_key](DigestWriter::enter_key) and [exit](DigestWriter::exit)
+///
+/// ```rust
+/// use digestible::format::Format;
+/// use digestible::{DigestWriter, Digestible};
+/// pub struct Counting<W> {
+///     pub inner: W,
+///     pub bytes: usize,
+/// }
+/// impl<W: DigestWriter> DigestWriter for Counting<W> {
+///     const FORMAT: Format = W::FORMAT;
+///     const TRACES_PATHS: bool = W::TRACES_PATHS;
+///     fn write(&mut self, data: &[u8]) {
+///         self.bytes += data.len();
+///         self.inner.write(data);
+///     }
+///     fn enter_field(&mut self, name: &str) {
+///         self.inner.enter_field(name)
+///     }
+///     fn enter_index(&mut self, index: usize) {
+///         self.inner.enter_index(index)
+///     }
+///     fn enter_key<K: Digestible + ?Sized>(&mut self, key: &K) {
+///         self.inner.enter_key(key)
+///     }
+///     fn exit(&mut self) {
+///         self.inner.exit()
+///     }
+///     fn digest_key(&self) -> Option<&[u8]> {
+///         self.inner.digest_key()
+///     }
+/// }
+/// ```
 pub trait DigestWriter {
+    /// The encoding format [Digestible](crate::Digestible) implementations use with this writer.
+    ///
+    /// Defaults to [Legacy](crate::format::Legacy). Wrappers must forward it. See [Wrapping Writers](DigestWriter#wrapping-writers).
+    /// Use [FormatWriter](crate::format::FormatWriter) to select another. See [format](crate::format)
+    const FORMAT: Format = Format::Legacy;
+    /// If the writer uses the path hooks [enter_field](DigestWriter::enter_field),
+    /// [enter_index](DigestWriter::enter_index), [enter_key](DigestWriter::enter_key) and [exit](DigestWriter::exit).
+    ///
+    /// Defaults to false. Collections that digest their items into a buffer first
+    /// digest the items again into the writer when this is true. So the hooks are called for them
+    const TRACES_PATHS: bool = false;
     /// Writes the data to the underlying writer.
     ///
-    /// This is the only function that is required to be implemented.
+    /// This is the only function that is required to be implemented. Wrappers must forward more. See [Wrapping Writers](DigestWriter#wrapping-writers)
     fn write(&mut self, data: &[u8]);
     /// Writes a [`bool`](bool) to the underlying writer
     ///
@@ -154,8 +224,47 @@ pub trait DigestWriter {
         #[doc = write_doc!(f32, write_f32, float)]
         (f64, write_f64, 8, write_f64)
     );
+    /// Called before a field of a struct or enum variant is digested. Followed by [exit](DigestWriter::exit)
+    ///
+    /// Tuple struct fields use their index as the name.
+    /// The hooks never change the digest. See [diff](crate::diff)
+    ///
+    /// # Default Implementation
+    /// Does nothing
+    #[inline(always)]
+    fn enter_field(&mut self, _name: &str) {}
+    /// Called before an item of a sequence is digested. Followed by [exit](DigestWriter::exit)
+    ///
+    /// # Default Implementation
+    /// Does nothing
+    #[inline(always)]
+    fn enter_index(&mut self, _index: usize) {}
+    /// Called after the key of a map entry is digested and before its value. Followed by [exit](DigestWriter::exit)
+    ///
+    /// Items of sets are digested between `enter_key` with the item and `exit`
+    ///
+    /// # Default Implementation
+    /// Does nothing
+    #[inline(always)]
+    fn enter_key<K: Digestible + ?Sized>(&mut self, _key: &K) {}
+    /// Called after the value of the last `enter_*` hook was digested
+    ///
+    /// # Default Implementation
+    /// Does nothing
+    #[inline(always)]
+    fn exit(&mut self) {}
+    /// The secret key used by `salted` and `keyed` fields. Set with [KeyedWriter](crate::keyed::KeyedWriter)
+    ///
+    /// # Default Implementation
+    /// Returns None
+    #[inline(always)]
+    fn digest_key(&self) -> Option<&[u8]> {
+        None
+    }
 }
-impl<'a, T: DigestWriter> DigestWriter for &'a mut T {
+impl<T: DigestWriter + ?Sized> DigestWriter for &mut T {
+    const FORMAT: Format = T::FORMAT;
+    const TRACES_PATHS: bool = T::TRACES_PATHS;
     deref_and_call_inner!();
 }
 #[cfg(feature = "alloc")]
@@ -169,7 +278,9 @@ mod has_alloc {
             self.extend_from_slice(data);
         }
     }
-    impl<T: DigestWriter> DigestWriter for Box<T> {
+    impl<T: DigestWriter + ?Sized> DigestWriter for Box<T> {
+        const FORMAT: crate::format::Format = T::FORMAT;
+        const TRACES_PATHS: bool = T::TRACES_PATHS;
         deref_and_call_inner!();
     }
 }
@@ -1,4 +1,5 @@
 use alloc::collections::{BTreeMap, BTreeSet, BinaryHeap, LinkedList, VecDeque};
+use alloc::ffi::CString;
 
 use crate::digestible::internal_macros::{as_ref_then_call_inner, impl_for_as_ref_u8};
 use crate::digestible::Digestible;
@@ -11,7 +12,74 @@ use alloc::sync::{Arc, Weak as WeakArc};
 use alloc::vec::Vec;
 use byteorder::ByteOrder;
 
-use super::core_types::{digest_iter, digest_native_iter};
+use super::core_types::{digest_iter, digest_map_iter, digest_native_iter, digest_native_map_iter};
+use crate::format::{is_v1, write_len, FormatBuffer};
+
+/// Digests every item into its own buffer and writes them sorted by their bytes.
+/// V1 writes the length first
+///
+/// Used for collections where the iteration order is not stable.
+/// Every item is digested inside of [enter_key](DigestWriter::enter_key) with the item
+#[inline]
+pub(crate) fn digest_unordered<Item, B, W, I>(iter: I, writer: &mut W)
+where
+    Item: Digestible,
+    B: ByteOrder,
+    W: DigestWriter,
+    I: ExactSizeIterator<Item = Item>,
+{
+    write_sorted::<_, B, W, _>(iter, writer, |item, writer| {
+        writer.enter_key(item);
+        item.digest::<B, W>(writer);
+        writer.exit();
+    });
+}
+/// [digest_unordered] for the entries of a map. The values are digested inside of [enter_key](DigestWriter::enter_key)
+#[cfg(feature = "std")]
+#[inline]
+pub(crate) fn digest_unordered_map<'a, K, V, B, W, I>(iter: I, writer: &mut W)
+where
+    K: Digestible + 'a,
+    V: Digestible + 'a,
+    B: ByteOrder,
+    W: DigestWriter,
+    I: ExactSizeIterator<Item = (&'a K, &'a V)>,
+{
+    write_sorted::<_, B, W, _>(
+        iter.map(super::core_types::MapEntry::from),
+        writer,
+        |entry, writer| entry.digest::<B, W>(writer),
+    );
+}
+/// Sorts the items by their bytes.
+///
+/// The buffers are written if the writer does not [trace paths](DigestWriter::TRACES_PATHS).
+/// Otherwise the items are digested again with `trace` in the same order
+fn write_sorted<Item, B, W, I>(iter: I, writer: &mut W, trace: impl Fn(&Item, &mut W))
+where
+    Item: Digestible,
+    B: ByteOrder,
+    W: DigestWriter,
+    I: ExactSizeIterator<Item = Item>,
+{
+    write_len::<B, W>(writer, iter.len());
+    let key = writer.digest_key();
+    let mut items: Vec<(Vec<u8>, Item)> = iter
+        .map(|item| {
+            let mut buffer = FormatBuffer::<W>::new(key);
+            item.digest::<B, _>(&mut buffer);
+            (buffer.buffer, item)
+        })
+        .collect();
+    items.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+    for (buffer, item) in items {
+        if W::TRACES_PATHS {
+            trace(&item, writer);
+        } else {
+            writer.write(&buffer);
+        }
+    }
+}
 
 impl<T: Digestible> Digestible for Vec<T> {
     fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
@@ -23,6 +91,11 @@ impl<T: Digestible> Digestible for Vec<T> {
 }
 impl_for_as_ref_u8!(String);
 
+/// Writes the bytes without the nul terminator
+impl Digestible for CString {
+    as_ref_then_call_inner!();
+}
+
 impl<D: Digestible> Digestible for Box<D> {
     as_ref_then_call_inner!();
 }
@@ -54,12 +127,14 @@ impl<V: Digestible> Digestible for BTreeSet<V> {
         digest_native_iter::<_, W, _>(self.iter(), writer);
     }
 }
+/// Legacy writes the items in the internal order of the heap. V1 sorts them by their bytes
 impl<V: Digestible> Digestible for BinaryHeap<V> {
     fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
-        digest_iter::<_, B, W, _>(self.iter(), writer);
-    }
-    fn digest_native<W: DigestWriter>(&self, writer: &mut W) {
-        digest_native_iter::<_, W, _>(self.iter(), writer);
+        if is_v1::<W>() {
+            digest_unordered::<_, B, W, _>(self.iter(), writer);
+        } else {
+            digest_iter::<_, B, W, _>(self.iter(), writer);
+        }
     }
 }
 impl<V: Digestible> Digestible for VecDeque<V> {
@@ -81,10 +156,10 @@ impl<V: Digestible> Digestible for LinkedList<V> {
 
 impl<K: Digestible, V: Digestible> Digestible for BTreeMap<K, V> {
     fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
-        digest_iter::<_, B, W, _>(self.iter(), writer);
+        digest_map_iter::<_, _, B, W, _>(self.iter(), writer);
     }
     fn digest_native<W: DigestWriter>(&self, writer: &mut W) {
-        digest_native_iter::<_, W, _>(self.iter(), writer);
+        digest_native_map_iter::<_, _, W, _>(self.iter(), writer);
     }
 }
 
@@ -1,20 +1,26 @@
-use crate::digestible::internal_macros::impl_for_hashable_hack;
 use crate::digestible::Digestible;
+use crate::format::{is_v1, write_bytes, write_len, write_tag};
 use crate::DigestWriter;
 use byteorder::ByteOrder;
+use core::cell::{Cell, RefCell};
+use core::cmp::{Ordering, Reverse};
+use core::ffi::CStr;
 use core::marker::PhantomData;
+use core::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, Saturating, Wrapping,
+};
+use core::ops::{Bound, Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
 
-impl<'a, T: Digestible> Digestible for &'a [T] {
+impl<T: Digestible> Digestible for &[T] {
     #[inline(always)]
     fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
-        for item in *self {
-            item.digest::<B, W>(writer)
-        }
+        digest_iter::<_, B, W, _>(self.iter(), writer);
     }
 }
-impl<'str> Digestible for &'str str {
+impl Digestible for &str {
     fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
-        writer.write(self.as_bytes())
+        write_bytes::<B, W>(writer, self.as_bytes())
     }
 }
 
@@ -23,9 +29,14 @@ impl Digestible for bool {
         writer.write(&[*self as u8])
     }
 }
+/// Legacy writes the lowest byte. V1 writes the unicode scalar value as a u32
 impl Digestible for char {
     fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
-        writer.write(&[*self as u8])
+        if is_v1::<W>() {
+            writer.write_u32::<B>(*self as u32)
+        } else {
+            writer.write(&[*self as u8])
+        }
     }
 }
 
@@ -62,62 +73,365 @@ digestible_for_num!(i16, write_i16);
 digestible_for_num!(i32, write_i32);
 digestible_for_num!(i64, write_i64);
 digestible_for_num!(i128, write_i128);
-digestible_for_num!(f32, write_f32);
-digestible_for_num!(f64, write_f64);
 
-digestible_for_num!(usize, write_usize);
-digestible_for_num!(isize, write_isize);
+macro_rules! digestible_for_float {
+    ($float:ty, $write:ident, $nan:expr) => {
+        /// V1 writes every NaN as the canonical quiet NaN
+        impl Digestible for $float {
+            #[inline(always)]
+            fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
+                if is_v1::<W>() && self.is_nan() {
+                    writer.$write::<B>(<$float>::from_bits($nan))
+                } else {
+                    writer.$write::<B>(*self)
+                }
+            }
+        }
+    };
+}
+digestible_for_float!(f32, write_f32, 0x7FC0_0000);
+digestible_for_float!(f64, write_f64, 0x7FF8_0000_0000_0000);
+
+macro_rules! digestible_for_size {
+    ($size:ty, $write:ident, $write_v1:ident, $as:ty) => {
+        /// Legacy uses the width of the platform. V1 always writes 64 bits
+        impl Digestible for $size {
+            #[inline(always)]
+            fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
+                if is_v1::<W>() {
+                    writer.$write_v1::<B>(*self as $as)
+                } else {
+                    writer.$write::<B>(*self)
+                }
+            }
+        }
+    };
+}
+digestible_for_size!(usize, write_usize, write_u64, u64);
+digestible_for_size!(isize, write_isize, write_i64, i64);
 
+/// Legacy only writes the value of Some. V1 writes the u8 tag `0` for None and `1` for Some
 impl<T: Digestible> Digestible for Option<T> {
     #[inline]
     fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
-        if let Some(value) = self {
-            value.digest::<B, W>(writer);
+        match self {
+            Some(value) => {
+                write_tag(writer, 1);
+                value.digest::<B, W>(writer);
+            }
+            None => write_tag(writer, 0),
         }
     }
 }
+/// Legacy only writes the value. V1 writes the u8 tag `0` for Ok and `1` for Err first
 impl<S: Digestible, E: Digestible> Digestible for Result<S, E> {
     #[inline]
     fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
         match self {
             Ok(value) => {
+                write_tag(writer, 0);
                 value.digest::<B, W>(writer);
             }
             Err(value) => {
+                write_tag(writer, 1);
+                value.digest::<B, W>(writer);
+            }
+        }
+    }
+}
+
+macro_rules! digestible_for_non_zero {
+    ($($non_zero:ty),*) => {
+        $(
+            impl Digestible for $non_zero {
+                /// Written the same as the primitive value
+                #[inline(always)]
+                fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
+                    self.get().digest::<B, W>(writer)
+                }
+            }
+        )*
+    };
+}
+digestible_for_non_zero!(
+    NonZeroU8,
+    NonZeroU16,
+    NonZeroU32,
+    NonZeroU64,
+    NonZeroU128,
+    NonZeroUsize,
+    NonZeroI8,
+    NonZeroI16,
+    NonZeroI32,
+    NonZeroI64,
+    NonZeroI128,
+    NonZeroIsize
+);
+/// Written the same as the inner value
+impl<T: Digestible> Digestible for Wrapping<T> {
+    #[inline(always)]
+    fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
+        self.0.digest::<B, W>(writer)
+    }
+}
+/// Written the same as the inner value
+impl<T: Digestible> Digestible for Saturating<T> {
+    #[inline(always)]
+    fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
+        self.0.digest::<B, W>(writer)
+    }
+}
+/// Written the same as the inner value
+impl<T: Digestible> Digestible for Reverse<T> {
+    #[inline(always)]
+    fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
+        self.0.digest::<B, W>(writer)
+    }
+}
+/// Writes `-1`, `0` or `1` as an i8
+impl Digestible for Ordering {
+    #[inline(always)]
+    fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
+        writer.write_i8(*self as i8)
+    }
+}
+/// Digests a copy of the value
+impl<T: Digestible + Copy> Digestible for Cell<T> {
+    #[inline(always)]
+    fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
+        self.get().digest::<B, W>(writer)
+    }
+}
+/// Digests the borrowed value
+///
+/// # Panics
+/// If the value is currently mutably borrowed
+impl<T: Digestible + ?Sized> Digestible for RefCell<T> {
+    #[inline(always)]
+    fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
+        self.borrow().digest::<B, W>(writer)
+    }
+}
+/// Writes the start then the end
+impl<T: Digestible> Digestible for Range<T> {
+    #[inline(always)]
+    fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
+        self.start.digest::<B, W>(writer);
+        self.end.digest::<B, W>(writer);
+    }
+}
+/// Writes the start then the end. V1 writes a u8 `1` first to mark the end as inclusive
+impl<T: Digestible> Digestible for RangeInclusive<T> {
+    #[inline(always)]
+    fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
+        write_tag(writer, 1);
+        self.start().digest::<B, W>(writer);
+        self.end().digest::<B, W>(writer);
+    }
+}
+/// Writes the start
+impl<T: Digestible> Digestible for RangeFrom<T> {
+    #[inline(always)]
+    fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
+        self.start.digest::<B, W>(writer);
+    }
+}
+/// Writes the end
+impl<T: Digestible> Digestible for RangeTo<T> {
+    #[inline(always)]
+    fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
+        self.end.digest::<B, W>(writer);
+    }
+}
+/// Writes the end. V1 writes a u8 `1` first to mark the end as inclusive
+impl<T: Digestible> Digestible for RangeToInclusive<T> {
+    #[inline(always)]
+    fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
+        write_tag(writer, 1);
+        self.end.digest::<B, W>(writer);
+    }
+}
+/// Writes nothing
+impl Digestible for RangeFull {
+    #[inline(always)]
+    fn digest<B: ByteOrder, W: DigestWriter>(&self, _: &mut W) {}
+}
+/// Writes a u8 tag followed by the value.
+///
+/// `0` for Included, `1` for Excluded and `2` for Unbounded
+impl<T: Digestible> Digestible for Bound<T> {
+    #[inline]
+    fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
+        match self {
+            Bound::Included(value) => {
+                writer.write_u8(0);
+                value.digest::<B, W>(writer);
+            }
+            Bound::Excluded(value) => {
+                writer.write_u8(1);
                 value.digest::<B, W>(writer);
             }
+            Bound::Unbounded => writer.write_u8(2),
         }
     }
 }
+/// Writes the bytes without the nul terminator
+impl Digestible for CStr {
+    #[inline(always)]
+    fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
+        write_bytes::<B, W>(writer, self.to_bytes())
+    }
+}
 
 impl<T: ?Sized> Digestible for PhantomData<T> {
     #[inline(always)]
     fn digest<B: ByteOrder, W: DigestWriter>(&self, _: &mut W) {}
 }
-impl_for_hashable_hack!(core::time::Duration);
+/// Writes the seconds as a u64 then the subsecond nanoseconds as a u32
+impl Digestible for core::time::Duration {
+    #[inline(always)]
+    fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
+        writer.write_u64::<B>(self.as_secs());
+        writer.write_u32::<B>(self.subsec_nanos());
+    }
+}
 
-/// Digests an interator of digestible items
+/// Digests an interator of digestible items. V1 writes the length first
+///
+/// Every item is digested inside of [enter_index](DigestWriter::enter_index)
 #[inline(always)]
 pub(crate) fn digest_iter<'item, Item, B, W, I>(iter: I, writer: &mut W)
 where
     Item: Digestible + 'item,
     B: ByteOrder,
     W: DigestWriter,
-    I: Iterator<Item = Item>,
+    I: ExactSizeIterator<Item = Item>,
 {
-    for item in iter {
+    write_len::<B, W>(writer, iter.len());
+    for (index, item) in iter.enumerate() {
+        writer.enter_index(index);
         item.digest::<B, W>(writer);
+        writer.exit();
     }
 }
-/// Digests an interator of digestible items
+/// Digests an interator of digestible items. V1 writes the length first
+///
+/// Every item is digested inside of [enter_index](DigestWriter::enter_index)
 #[inline(always)]
 pub(crate) fn digest_native_iter<'item, Item, W, I>(iter: I, writer: &mut W)
 where
     Item: Digestible + 'item,
     W: DigestWriter,
-    I: Iterator<Item = Item>,
+    I: ExactSizeIterator<Item = Item>,
 {
-    for item in iter {
+    write_len::<byteorder::NativeEndian, W>(writer, iter.len());
+    for (index, item) in iter.enumerate() {
+        writer.enter_index(index);
         item.digest_native::<W>(writer);
+        writer.exit();
+    }
+}
+/// A key and value of a map.
+///
+/// Digested the same as the tuple `(key, value)`. The value is digested inside of [enter_key](DigestWriter::enter_key)
+#[cfg(feature = "alloc")]
+pub(crate) struct MapEntry<'a, K: ?Sized, V: ?Sized>(pub(crate) &'a K, pub(crate) &'a V);
+#[cfg(feature = "alloc")]
+impl<'a, K: ?Sized, V: ?Sized> From<(&'a K, &'a V)> for MapEntry<'a, K, V> {
+    #[inline(always)]
+    fn from((key, value): (&'a K, &'a V)) -> Self {
+        Self(key, value)
+    }
+}
+#[cfg(feature = "alloc")]
+impl<K: Digestible + ?Sized, V: Digestible + ?Sized> Digestible for MapEntry<'_, K, V> {
+    #[inline(always)]
+    fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
+        self.0.digest::<B, W>(writer);
+        writer.enter_key(self.0);
+        self.1.digest::<B, W>(writer);
+        writer.exit();
+    }
+    #[inline(always)]
+    fn digest_native<W: DigestWriter>(&self, writer: &mut W) {
+        self.0.digest_native::<W>(writer);
+        writer.enter_key(self.0);
+        self.1.digest_native::<W>(writer);
+        writer.exit();
+    }
+}
+/// Digests the entries of a map in the order of the iterator. V1 writes the length first
+#[cfg(feature = "alloc")]
+#[inline(always)]
+pub(crate) fn digest_map_iter<'a, K, V, B, W, I>(iter: I, writer: &mut W)
+where
+    K: Digestible + ?Sized + 'a,
+    V: Digestible + ?Sized + 'a,
+    B: ByteOrder,
+    W: DigestWriter,
+    I: ExactSizeIterator<Item = (&'a K, &'a V)>,
+{
+    write_len::<B, W>(writer, iter.len());
+    for entry in iter {
+        MapEntry::from(entry).digest::<B, W>(writer);
+    }
+}
+/// Digests the entries of a map in the order of the iterator. V1 writes the length first
+#[cfg(feature = "alloc")]
+#[inline(always)]
+pub(crate) fn digest_native_map_iter<'a, K, V, W, I>(iter: I, writer: &mut W)
+where
+    K: Digestible + ?Sized + 'a,
+    V: Digestible + ?Sized + 'a,
+    W: DigestWriter,
+    I: ExactSizeIterator<Item = (&'a K, &'a V)>,
+{
+    write_len::<byteorder::NativeEndian, W>(writer, iter.len());
+    for entry in iter {
+        MapEntry::from(entry).digest_native::<W>(writer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Digestible;
+    use byteorder::LittleEndian;
+    use core::cell::RefCell;
+    use core::cmp::Ordering;
+    use core::num::{NonZeroU32, Wrapping};
+    use core::ops::Bound;
+
+    fn digest(value: &impl Digestible) -> Vec<u8> {
+        let mut digest = Vec::new();
+        value.digest::<LittleEndian, _>(&mut digest);
+        digest
+    }
+
+    #[test]
+    fn wrappers() {
+        assert_eq!(digest(&NonZeroU32::new(1).unwrap()), digest(&1u32));
+        assert_eq!(digest(&Wrapping(1u32)), digest(&1u32));
+        assert_eq!(digest(&RefCell::new(1u32)), digest(&1u32));
+    }
+
+    #[test]
+    fn ranges() {
+        assert_eq!(digest(&(1u8..2)), vec![1, 2]);
+        assert_eq!(digest(&(1u8..=2)), vec![1, 2]);
+        assert_eq!(digest(&(..2u8)), vec![2]);
+        assert_eq!(digest(&Bound::Excluded(5u8)), vec![1, 5]);
+        assert_eq!(digest(&Bound::<u8>::Unbounded), vec![2]);
+    }
+
+    #[test]
+    fn ordering() {
+        assert_eq!(digest(&Ordering::Less), vec![0xFF]);
+        assert_eq!(digest(&Ordering::Greater), vec![1]);
+    }
+
+    #[test]
+    fn c_str() {
+        let c_str = core::ffi::CStr::from_bytes_with_nul(b"hi\0").unwrap();
+        assert_eq!(digest(&c_str), b"hi");
     }
 }
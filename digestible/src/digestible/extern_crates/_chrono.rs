@@ -1,57 +1,146 @@
-use core::hash::Hash;
-
+//! Explicit encodings for chrono types.
+//!
+//! These do not depend on the internal representation of chrono.
+//! So the digest stays the same between chrono releases.
 use chrono::{
-    DateTime, Days, Duration, Month, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Weekday,
+    DateTime, Datelike, Days, Duration, Month, NaiveDate, NaiveDateTime, NaiveTime, Offset,
+    TimeZone, Timelike, Weekday,
 };
 
-use crate::{hash_digester::HashableHack, Digestible};
+use crate::Digestible;
 
+/// Writes the seconds since the unix epoch as an i64 and the nanoseconds as a u32
 impl Digestible for NaiveDateTime {
     fn digest<B: byteorder::ByteOrder, W: crate::DigestWriter>(&self, writer: &mut W) {
-        let mut hashable_hack = HashableHack(writer);
-        self.hash(&mut hashable_hack);
+        let utc = self.and_utc();
+        writer.write_i64::<B>(utc.timestamp());
+        writer.write_u32::<B>(utc.timestamp_subsec_nanos());
     }
 }
+/// Writes the UTC seconds since the unix epoch as an i64, the nanoseconds as a u32
+/// and the offset from UTC in seconds as an i32.
+///
+/// Use [digest_normalized](crate::digest_with::chrono::digest_normalized) to ignore the offset
 impl<Tz: TimeZone> Digestible for DateTime<Tz> {
     fn digest<B: byteorder::ByteOrder, W: crate::DigestWriter>(&self, writer: &mut W) {
-        let mut hashable_hack = HashableHack(writer);
-        self.hash(&mut hashable_hack);
+        writer.write_i64::<B>(self.timestamp());
+        writer.write_u32::<B>(self.timestamp_subsec_nanos());
+        writer.write_i32::<B>(self.offset().fix().local_minus_utc());
     }
 }
+/// Writes the whole seconds as an i64 and the nanoseconds as an i32.
+///
+/// Both have the same sign
 impl Digestible for Duration {
     fn digest<B: byteorder::ByteOrder, W: crate::DigestWriter>(&self, writer: &mut W) {
-        let mut hashable_hack = HashableHack(writer);
-        self.hash(&mut hashable_hack);
+        writer.write_i64::<B>(self.num_seconds());
+        writer.write_i32::<B>(self.subsec_nanos());
     }
 }
+/// Writes the days since January 1st of year 1 in the proleptic Gregorian calendar as an i32
+///
+/// January 1st of year 1 is `1`
 impl Digestible for NaiveDate {
     fn digest<B: byteorder::ByteOrder, W: crate::DigestWriter>(&self, writer: &mut W) {
-        let mut hashable_hack = HashableHack(writer);
-        self.hash(&mut hashable_hack);
+        writer.write_i32::<B>(self.num_days_from_ce());
     }
 }
 
+/// Writes the days since Monday as a u8
 impl Digestible for Weekday {
     fn digest<B: byteorder::ByteOrder, W: crate::DigestWriter>(&self, writer: &mut W) {
-        writer.write_u8(*self as u8);
+        writer.write_u8(self.num_days_from_monday() as u8);
     }
 }
+/// Writes the month as a u8 starting at `0` for January
 impl Digestible for Month {
     fn digest<B: byteorder::ByteOrder, W: crate::DigestWriter>(&self, writer: &mut W) {
-        writer.write_u8(*self as u8);
+        writer.write_u8(self.number_from_month() as u8 - 1);
     }
 }
 
+/// Writes the number of days as a u64.
+///
+/// Days does not expose the number. It is found by comparing against [Days::new] one bit at a time
 impl Digestible for Days {
     fn digest<B: byteorder::ByteOrder, W: crate::DigestWriter>(&self, writer: &mut W) {
-        let mut hashable_hack = HashableHack(writer);
-        self.hash(&mut hashable_hack);
+        let mut days = 0u64;
+        for bit in (0..u64::BITS).rev() {
+            let candidate = days | (1 << bit);
+            if Days::new(candidate) <= *self {
+                days = candidate;
+            }
+        }
+        writer.write_u64::<B>(days);
     }
 }
 
+/// Writes the seconds since midnight as a u32 and the nanoseconds as a u32
+///
+/// The nanoseconds can be over `999_999_999` for a leap second
 impl Digestible for NaiveTime {
     fn digest<B: byteorder::ByteOrder, W: crate::DigestWriter>(&self, writer: &mut W) {
-        let mut hashable_hack = HashableHack(writer);
-        self.hash(&mut hashable_hack);
+        writer.write_u32::<B>(self.num_seconds_from_midnight());
+        writer.write_u32::<B>(self.nanosecond());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::digest_with::chrono::digest_normalized;
+    use crate::Digestible;
+    use byteorder::BigEndian;
+    use chrono::{DateTime, Days, FixedOffset, NaiveDate, NaiveTime, TimeZone, Utc};
+
+    fn digest(value: &impl Digestible) -> Vec<u8> {
+        let mut digest = Vec::new();
+        value.digest::<BigEndian, _>(&mut digest);
+        digest
+    }
+
+    #[test]
+    fn naive() {
+        let date = NaiveDate::from_ymd_opt(1, 1, 1).unwrap();
+        assert_eq!(digest(&date), 1i32.to_be_bytes());
+
+        let time = NaiveTime::from_hms_nano_opt(0, 1, 2, 3).unwrap();
+        assert_eq!(
+            digest(&time),
+            [62u32.to_be_bytes(), 3u32.to_be_bytes()].concat()
+        );
+
+        let date_time = NaiveDate::from_ymd_opt(1970, 1, 2)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        assert_eq!(
+            digest(&date_time),
+            [86400i64.to_be_bytes().as_slice(), &0u32.to_be_bytes()].concat()
+        );
+        assert_eq!(digest(&Days::new(2)), 2u64.to_be_bytes());
+        assert_eq!(digest(&Days::new(0)), 0u64.to_be_bytes());
+        assert_eq!(digest(&Days::new(u64::MAX)), u64::MAX.to_be_bytes());
+    }
+
+    #[test]
+    fn date_time() {
+        let utc: DateTime<Utc> = Utc.timestamp_opt(10, 5).unwrap();
+        let offset = utc.with_timezone(&FixedOffset::east_opt(3600).unwrap());
+        assert_eq!(
+            digest(&utc),
+            [
+                10i64.to_be_bytes().as_slice(),
+                &5u32.to_be_bytes(),
+                &0i32.to_be_bytes()
+            ]
+            .concat()
+        );
+        assert_ne!(digest(&utc), digest(&offset));
+
+        let mut normalized_utc = Vec::new();
+        digest_normalized::<BigEndian, _>(&utc, &mut normalized_utc);
+        let mut normalized_offset = Vec::new();
+        digest_normalized::<BigEndian, _>(&offset, &mut normalized_offset);
+        assert_eq!(normalized_utc, normalized_offset);
     }
 }
@@ -1,9 +1,19 @@
+use alloc::string::String;
 use serde_json::{Number, Value};
 
-use crate::{digestible::core_types::digest_iter, DigestWriter, Digestible};
+use crate::digest_with::json::digest_tagged;
+use crate::format::is_v1;
+use crate::{digestible::core_types::digest_map_iter, DigestWriter, Digestible};
 /// Implement [`Digestible`] for [`serde_json::Value`].
+///
+/// Legacy writes values without any framing.
+/// V1 uses the [tagged](crate::digest_with::json::digest_tagged) encoding.
+/// Use [json](crate::digest_with::json) for canonical JSON.
 impl Digestible for Value {
     fn digest<B: byteorder::ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
+        if is_v1::<W>() {
+            return digest_tagged::<B, W>(self, writer);
+        }
         match self {
             Value::Null => writer.write_u8(0),
             Value::Bool(b) => writer.write_bool(*b),
@@ -13,7 +23,7 @@ impl Digestible for Value {
             Value::String(s) => s.digest::<B, W>(writer),
             Value::Array(a) => a.digest::<B, W>(writer),
             Value::Object(o) => {
-                digest_iter::<(&String, &Value), B, W, _>(o.iter(), writer);
+                digest_map_iter::<String, Value, B, W, _>(o.iter(), writer);
             }
         }
     }
@@ -55,6 +65,11 @@ mod tests {
         });
         let mut digest = Vec::new();
         value.digest::<LittleEndian, _>(&mut digest);
-        println!("{:?}", digest);
+        let mut expected = Vec::new();
+        for (key, value) in [("a", 1i64), ("b", 2), ("c", 3), ("d", 4)] {
+            expected.extend_from_slice(key.as_bytes());
+            expected.extend_from_slice(&value.to_le_bytes());
+        }
+        assert_eq!(digest, expected);
     }
 }
@@ -0,0 +1,148 @@
+//! Explicit encodings for time types.
+//!
+//! The encodings are the same as the matching chrono types.
+//! So the same value digested from either library has the same digest.
+use time::{Date, Duration, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset, Weekday};
+
+use crate::Digestible;
+
+/// The julian day of January 1st of year 1 minus one.
+const JULIAN_DAY_OFFSET: i32 = 1_721_425;
+
+/// Writes the UTC seconds since the unix epoch as an i64, the nanoseconds as a u32
+/// and the offset from UTC in seconds as an i32.
+///
+/// Same as `chrono::DateTime`.
+/// Use [digest_normalized](crate::digest_with::time::digest_normalized) to ignore the offset
+impl Digestible for OffsetDateTime {
+    fn digest<B: byteorder::ByteOrder, W: crate::DigestWriter>(&self, writer: &mut W) {
+        writer.write_i64::<B>(self.unix_timestamp());
+        writer.write_u32::<B>(self.nanosecond());
+        self.offset().digest::<B, W>(writer);
+    }
+}
+/// Writes the seconds since the unix epoch as an i64 and the nanoseconds as a u32
+///
+/// Same as `chrono::NaiveDateTime`
+impl Digestible for PrimitiveDateTime {
+    fn digest<B: byteorder::ByteOrder, W: crate::DigestWriter>(&self, writer: &mut W) {
+        let utc = self.assume_utc();
+        writer.write_i64::<B>(utc.unix_timestamp());
+        writer.write_u32::<B>(utc.nanosecond());
+    }
+}
+/// Writes the days since January 1st of year 1 in the proleptic Gregorian calendar as an i32
+///
+/// January 1st of year 1 is `1`. Same as `chrono::NaiveDate`
+impl Digestible for Date {
+    fn digest<B: byteorder::ByteOrder, W: crate::DigestWriter>(&self, writer: &mut W) {
+        writer.write_i32::<B>(self.to_julian_day() - JULIAN_DAY_OFFSET);
+    }
+}
+/// Writes the seconds since midnight as a u32 and the nanoseconds as a u32
+///
+/// Same as `chrono::NaiveTime`
+impl Digestible for Time {
+    fn digest<B: byteorder::ByteOrder, W: crate::DigestWriter>(&self, writer: &mut W) {
+        let (hour, minute, second, nanosecond) = self.as_hms_nano();
+        writer.write_u32::<B>(hour as u32 * 3600 + minute as u32 * 60 + second as u32);
+        writer.write_u32::<B>(nanosecond);
+    }
+}
+/// Writes the offset from UTC in seconds as an i32
+impl Digestible for UtcOffset {
+    fn digest<B: byteorder::ByteOrder, W: crate::DigestWriter>(&self, writer: &mut W) {
+        writer.write_i32::<B>(self.whole_seconds());
+    }
+}
+/// Writes the whole seconds as an i64 and the nanoseconds as an i32.
+///
+/// Both have the same sign. Same as `chrono::TimeDelta`
+impl Digestible for Duration {
+    fn digest<B: byteorder::ByteOrder, W: crate::DigestWriter>(&self, writer: &mut W) {
+        writer.write_i64::<B>(self.whole_seconds());
+        writer.write_i32::<B>(self.subsec_nanoseconds());
+    }
+}
+/// Writes the month as a u8 starting at `0` for January
+impl Digestible for Month {
+    fn digest<B: byteorder::ByteOrder, W: crate::DigestWriter>(&self, writer: &mut W) {
+        writer.write_u8(*self as u8 - 1);
+    }
+}
+/// Writes the days since Monday as a u8
+impl Digestible for Weekday {
+    fn digest<B: byteorder::ByteOrder, W: crate::DigestWriter>(&self, writer: &mut W) {
+        writer.write_u8(self.number_days_from_monday());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Digestible;
+    use byteorder::BigEndian;
+    use time::{Date, Duration, Month, OffsetDateTime, Time, UtcOffset};
+
+    fn digest(value: &impl Digestible) -> Vec<u8> {
+        let mut digest = Vec::new();
+        value.digest::<BigEndian, _>(&mut digest);
+        digest
+    }
+
+    #[test]
+    fn date() {
+        let date = Date::from_calendar_date(1, Month::January, 1).unwrap();
+        assert_eq!(digest(&date), 1i32.to_be_bytes());
+    }
+
+    #[test]
+    fn offset_date_time() {
+        let date_time = OffsetDateTime::from_unix_timestamp_nanos(10_000_000_005)
+            .unwrap()
+            .to_offset(UtcOffset::from_whole_seconds(3600).unwrap());
+        assert_eq!(
+            digest(&date_time),
+            [
+                10i64.to_be_bytes().as_slice(),
+                &5u32.to_be_bytes(),
+                &3600i32.to_be_bytes()
+            ]
+            .concat()
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn same_as_chrono() {
+        use chrono::{Datelike, TimeZone};
+        let time_date = Date::from_calendar_date(2024, Month::February, 29).unwrap();
+        let chrono_date = chrono::NaiveDate::from_ymd_opt(2024, 2, 29).unwrap();
+        assert_eq!(digest(&time_date), digest(&chrono_date));
+        let time_date = Date::from_calendar_date(-44, Month::March, 15).unwrap();
+        let chrono_date = chrono::NaiveDate::from_ymd_opt(-44, 3, 15).unwrap();
+        assert_eq!(digest(&time_date), digest(&chrono_date));
+
+        let time_time = Time::from_hms_nano(13, 14, 15, 16).unwrap();
+        let chrono_time = chrono::NaiveTime::from_hms_nano_opt(13, 14, 15, 16).unwrap();
+        assert_eq!(digest(&time_time), digest(&chrono_time));
+
+        let time_date_time = time_date.with_time(time_time);
+        let chrono_date_time = chrono_date.and_time(chrono_time);
+        assert_eq!(digest(&time_date_time), digest(&chrono_date_time));
+
+        let offset = UtcOffset::from_whole_seconds(-7200).unwrap();
+        let time_offset = time_date_time.assume_offset(offset);
+        let chrono_offset = chrono::FixedOffset::west_opt(7200)
+            .unwrap()
+            .from_local_datetime(&chrono_date_time)
+            .unwrap();
+        assert_eq!(digest(&time_offset), digest(&chrono_offset));
+
+        let time_duration = Duration::new(-5, -10);
+        let chrono_duration = chrono::TimeDelta::new(-6, 999_999_990).unwrap();
+        assert_eq!(digest(&time_duration), digest(&chrono_duration));
+
+        assert_eq!(digest(&time_date.weekday()), digest(&chrono_date.weekday()));
+        assert_eq!(digest(&Month::March), digest(&chrono::Month::March));
+    }
+}
@@ -1,8 +1,15 @@
+use byteorder::ByteOrder;
 use uuid::Uuid;
 
-use crate::digestible::internal_macros::impl_for_as_ref_u8;
+use crate::{DigestWriter, Digestible};
 
-impl_for_as_ref_u8!(Uuid);
+/// Writes the 16 bytes. The size is fixed so no length is written in any format
+impl Digestible for Uuid {
+    #[inline(always)]
+    fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
+        writer.write(self.as_bytes())
+    }
+}
 
 #[cfg(test)]
 mod tests {
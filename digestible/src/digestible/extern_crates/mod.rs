@@ -8,3 +8,5 @@ mod _uuid;
 
 #[cfg(feature = "chrono")]
 mod _chrono;
+#[cfg(feature = "time")]
+mod _time;
@@ -1,10 +1,10 @@
 #[allow(unused_macros)]
 macro_rules! impl_for_hashable_hack {
     ($hashable:ty) => {
-        /// Implemented calling the hash method on the type
+        /// Implemented calling the hash method on the type. Integers are written with the ByteOrder `B`
         impl Digestible for $hashable {
             fn digest<B: byteorder::ByteOrder, W: $crate::DigestWriter>(&self, writer: &mut W) {
-                let mut hashable_hack = $crate::hash_digester::HashableHack(writer);
+                let mut hashable_hack = $crate::hash_digester::HashableHack::<_, B>::new(writer);
                 <Self as core::hash::Hash>::hash(self, &mut hashable_hack);
             }
         }
@@ -15,10 +15,11 @@ pub(super) use impl_for_hashable_hack;
 #[allow(unused_macros)]
 macro_rules! impl_for_as_ref_u8 {
     ($as_ref_u8:ty) => {
+        /// Writes the bytes. V1 writes the length first
         impl $crate::Digestible for $as_ref_u8 {
             #[inline(always)]
             fn digest<B: byteorder::ByteOrder, W: $crate::DigestWriter>(&self, writer: &mut W) {
-                writer.write(self.as_ref())
+                $crate::format::write_bytes::<B, W>(writer, self.as_ref())
             }
         }
     };
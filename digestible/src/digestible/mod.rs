@@ -1,8 +1,48 @@
 /*!
 # Default Implementation Notes
 
+These notes describe the [Legacy](crate::format::Legacy) format.
+See [format](crate::format) for the differences in [FormatV1](crate::format::FormatV1).
+
 ## Tuple Types
-They are written one after another into the digest.
+They are written one after another into the digest. Tuples of up to 16 elements are supported.
+
+## Wrapper Types
+`NonZero*`, [Wrapping](core::num::Wrapping), [Saturating](core::num::Saturating),
+[Reverse](core::cmp::Reverse), [Cell](core::cell::Cell), [RefCell](core::cell::RefCell),
+`Mutex` and `RwLock` are written the same as the value inside of them.
+
+`RefCell` panics if it is mutably borrowed. `Mutex` and `RwLock` are locked while digesting.
+
+## Ranges and Bounds
+Ranges write the start then the end. Only the bounds that exist are written.
+
+[Bound](core::ops::Bound) writes a u8 tag. `0` for Included, `1` for Excluded and `2` for Unbounded.
+Followed by the value.
+
+## Ordering
+[Ordering](core::cmp::Ordering) is written as an i8. `-1`, `0` or `1`
+
+## Network Addresses
+- `Ipv4Addr` and `Ipv6Addr` write their octets.
+- `IpAddr` and `SocketAddr` write a u8 tag `4` or `6` followed by the address.
+- Socket addresses write the ip then the port. `SocketAddrV6` then writes the flow info and scope id.
+
+## SystemTime
+Written as the offset from `UNIX_EPOCH`. Seconds as an i64 then nanoseconds as a u32.
+Times before the epoch have negative seconds.
+
+## CStr and CString
+The bytes without the nul terminator.
+
+## Path and OsStr
+[OsStr](std::ffi::OsStr) writes its encoded bytes. Valid UTF-8 is the same on every platform.
+[Path](std::path::Path) writes its normalized components separated by `/`.
+So the same relative path is digested the same on Windows and Unix.
+
+## HashMap and HashSet
+Each entry is digested separately and the entries are written sorted by their bytes.
+So the digest does not depend on the iteration order.
 
 ## Floats (f32, f64)
 Precision!
@@ -48,7 +88,7 @@ pub trait Digestible {
     }
 }
 
-impl<'a, D: Digestible> Digestible for &'a D {
+impl<D: Digestible + ?Sized> Digestible for &D {
     fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
         (*self).digest::<B, W>(writer)
     }
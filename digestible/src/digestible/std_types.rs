@@ -1,32 +1,201 @@
-use crate::digestible::internal_macros::impl_for_hashable_hack;
+use crate::digestible::alloc_types::{digest_unordered, digest_unordered_map};
+use crate::format::{is_v1, write_bytes};
 use crate::{DigestWriter, Digestible};
 use byteorder::ByteOrder;
 use std::collections::{HashMap, HashSet};
 use std::ffi::{OsStr, OsString};
-use std::path::{Path, PathBuf};
-
-use super::core_types::{digest_iter, digest_native_iter};
-impl_for_hashable_hack!(OsStr);
-impl_for_hashable_hack!(OsString);
-impl_for_hashable_hack!(PathBuf);
-impl_for_hashable_hack!(Path);
-
-impl<S, K: Digestible, V: Digestible> Digestible for HashMap<K, V, S> {
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Mutex, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+/// Writes the encoded bytes of the string.
+///
+/// Valid UTF-8 is written as UTF-8 on every platform.
+/// Anything else is written as the raw bytes on Unix and WTF-8 on Windows
+impl Digestible for OsStr {
+    #[inline(always)]
     fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
-        digest_iter::<_, B, W, _>(self.iter(), writer);
+        write_bytes::<B, W>(writer, self.as_encoded_bytes())
     }
-
-    fn digest_native<W: DigestWriter>(&self, writer: &mut W) {
-        digest_native_iter::<_, W, _>(self.iter(), writer);
+}
+/// Same as [OsStr]
+impl Digestible for OsString {
+    #[inline(always)]
+    fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
+        self.as_os_str().digest::<B, W>(writer)
+    }
+}
+/// Writes the normalized components of the path separated by `/`
+///
+/// - Repeated separators, trailing separators and `.` that is not at the start are removed.
+/// - The root is written as `/`. Windows prefixes such as `C:` are written as is.
+/// - Each component is written the same as an [OsStr]
+///
+/// So `a//b/` and `a\b` on Windows are both written as `a/b`.
+/// V1 writes the length of the normalized path first
+impl Digestible for Path {
+    fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
+        if is_v1::<W>() {
+            let mut buffer = Vec::new();
+            write_normalized_path(self, &mut buffer);
+            write_bytes::<B, W>(writer, &buffer);
+        } else {
+            write_normalized_path(self, writer);
+        }
+    }
+}
+fn write_normalized_path<W: DigestWriter>(path: &Path, writer: &mut W) {
+    let mut needs_separator = false;
+    for component in path.components() {
+        if needs_separator {
+            writer.write(b"/");
+        }
+        needs_separator = match component {
+            Component::Prefix(prefix) => {
+                writer.write(prefix.as_os_str().as_encoded_bytes());
+                false
+            }
+            Component::RootDir => {
+                writer.write(b"/");
+                false
+            }
+            Component::CurDir => {
+                writer.write(b".");
+                true
+            }
+            Component::ParentDir => {
+                writer.write(b"..");
+                true
+            }
+            Component::Normal(name) => {
+                writer.write(name.as_encoded_bytes());
+                true
+            }
+        };
+    }
+}
+/// Same as [Path]
+impl Digestible for PathBuf {
+    #[inline(always)]
+    fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
+        self.as_path().digest::<B, W>(writer)
     }
 }
 
+/// Each key and value is written one after another. Sorted by the bytes of the entry.
+/// V1 writes the number of entries first
+impl<S, K: Digestible, V: Digestible> Digestible for HashMap<K, V, S> {
+    fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
+        digest_unordered_map::<_, _, B, W, _>(self.iter(), writer);
+    }
+}
+/// Each value is written one after another. Sorted by the bytes of the value.
+/// V1 writes the number of values first
 impl<S, V: Digestible> Digestible for HashSet<V, S> {
     fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
-        digest_iter::<_, B, W, _>(self.iter(), writer);
+        digest_unordered::<_, B, W, _>(self.iter(), writer);
     }
-    fn digest_native<W: DigestWriter>(&self, writer: &mut W) {
-        digest_native_iter::<_, W, _>(self.iter(), writer);
+}
+/// Digests the value while holding the lock. A poisoned lock is still digested
+///
+/// # Deadlocks
+/// If the current thread already holds the lock
+impl<T: Digestible + ?Sized> Digestible for Mutex<T> {
+    fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
+        let guard = self.lock().unwrap_or_else(|error| error.into_inner());
+        guard.digest::<B, W>(writer)
+    }
+}
+/// Digests the value while holding a read lock. A poisoned lock is still digested
+///
+/// # Deadlocks
+/// If the current thread already holds the write lock
+impl<T: Digestible + ?Sized> Digestible for RwLock<T> {
+    fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
+        let guard = self.read().unwrap_or_else(|error| error.into_inner());
+        guard.digest::<B, W>(writer)
+    }
+}
+/// Writes the 4 octets
+impl Digestible for Ipv4Addr {
+    #[inline(always)]
+    fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
+        writer.write(&self.octets())
+    }
+}
+/// Writes the 16 octets
+impl Digestible for Ipv6Addr {
+    #[inline(always)]
+    fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
+        writer.write(&self.octets())
+    }
+}
+/// Writes the u8 tag `4` or `6` followed by the octets
+impl Digestible for IpAddr {
+    fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
+        match self {
+            IpAddr::V4(ip) => {
+                writer.write_u8(4);
+                ip.digest::<B, W>(writer);
+            }
+            IpAddr::V6(ip) => {
+                writer.write_u8(6);
+                ip.digest::<B, W>(writer);
+            }
+        }
+    }
+}
+/// Writes the ip then the port
+impl Digestible for SocketAddrV4 {
+    fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
+        self.ip().digest::<B, W>(writer);
+        writer.write_u16::<B>(self.port());
+    }
+}
+/// Writes the ip, port, flow info then the scope id
+impl Digestible for SocketAddrV6 {
+    fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
+        self.ip().digest::<B, W>(writer);
+        writer.write_u16::<B>(self.port());
+        writer.write_u32::<B>(self.flowinfo());
+        writer.write_u32::<B>(self.scope_id());
+    }
+}
+/// Writes the u8 tag `4` or `6` followed by the address
+impl Digestible for SocketAddr {
+    fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
+        match self {
+            SocketAddr::V4(addr) => {
+                writer.write_u8(4);
+                addr.digest::<B, W>(writer);
+            }
+            SocketAddr::V6(addr) => {
+                writer.write_u8(6);
+                addr.digest::<B, W>(writer);
+            }
+        }
+    }
+}
+/// The offset from [UNIX_EPOCH].
+/// Written as the seconds as an i64 followed by the nanoseconds as a u32.
+///
+/// Times before the epoch have negative seconds. The nanoseconds are always counted forward.
+/// So one nanosecond before the epoch is `-1` seconds and `999_999_999` nanoseconds.
+impl Digestible for SystemTime {
+    fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
+        let (secs, nanos) = match self.duration_since(UNIX_EPOCH) {
+            Ok(duration) => (duration.as_secs() as i64, duration.subsec_nanos()),
+            Err(error) => {
+                let duration = error.duration();
+                let secs = -(duration.as_secs() as i64);
+                match duration.subsec_nanos() {
+                    0 => (secs, 0),
+                    nanos => (secs - 1, 1_000_000_000 - nanos),
+                }
+            }
+        };
+        writer.write_i64::<B>(secs);
+        writer.write_u32::<B>(nanos);
     }
 }
 
@@ -34,6 +203,9 @@ impl<S, V: Digestible> Digestible for HashSet<V, S> {
 mod tests {
     use crate::Digestible;
     use std::collections::{HashMap, HashSet};
+    use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+    use std::path::Path;
+    use std::time::{Duration, UNIX_EPOCH};
 
     #[test]
     fn hash_map() {
@@ -44,7 +216,6 @@ mod tests {
         assert_eq!(digest, vec![107, 101, 121, 118, 97, 108, 117, 101]);
     }
 
-
     #[test]
     fn hashset() {
         let mut map = HashSet::new();
@@ -54,4 +225,56 @@ mod tests {
         map.digest::<byteorder::LittleEndian, _>(&mut digest);
         assert_eq!(digest, vec![107, 101, 121, 118, 97, 108, 117, 101]);
     }
+
+    #[test]
+    fn hashset_order() {
+        let set: HashSet<u32> = (0..64).collect();
+        let mut digest = Vec::new();
+        set.digest::<byteorder::BigEndian, _>(&mut digest);
+        let expected: Vec<u8> = (0..64u32).flat_map(|v| v.to_be_bytes()).collect();
+        assert_eq!(digest, expected);
+    }
+
+    #[test]
+    fn socket_addr() {
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 80);
+        let mut digest = Vec::new();
+        addr.digest::<byteorder::BigEndian, _>(&mut digest);
+        assert_eq!(digest, vec![4, 127, 0, 0, 1, 0, 80]);
+    }
+
+    #[test]
+    fn system_time() {
+        let mut digest = Vec::new();
+        (UNIX_EPOCH - Duration::from_nanos(1)).digest::<byteorder::BigEndian, _>(&mut digest);
+        let mut expected = (-1i64).to_be_bytes().to_vec();
+        expected.extend_from_slice(&999_999_999u32.to_be_bytes());
+        assert_eq!(digest, expected);
+    }
+
+    fn digest_path(path: &str) -> Vec<u8> {
+        let mut digest = Vec::new();
+        Path::new(path).digest::<byteorder::LittleEndian, _>(&mut digest);
+        digest
+    }
+
+    #[test]
+    fn path() {
+        assert_eq!(digest_path("a/b"), b"a/b");
+        assert_eq!(digest_path("a//b/"), b"a/b");
+        assert_eq!(digest_path("a/./b"), b"a/b");
+        assert_eq!(digest_path("./a/../b"), b"./a/../b");
+        assert_eq!(digest_path("/usr/lib"), b"/usr/lib");
+        assert_eq!(digest_path("/"), b"/");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn non_utf8_os_str() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+        let mut digest = Vec::new();
+        Path::new(OsStr::from_bytes(b"a/\xFF")).digest::<byteorder::LittleEndian, _>(&mut digest);
+        assert_eq!(digest, b"a/\xFF");
+    }
 }
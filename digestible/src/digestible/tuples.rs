@@ -9,22 +9,53 @@ impl Digestible for () {
 
 macro_rules! tuple_configs {
     ($($T:ident),*) => {
-        impl<$($T: Digestible),*> Digestible for ($($T),*) {
+        impl<$($T: Digestible),*> Digestible for ($($T,)*) {
             #[inline(always)]
             fn digest<ByteOrder: byteorder::ByteOrder, Writer: DigestWriter>(&self, writer: &mut Writer) {
-                let ($($T),*) = self;
+                let ($($T,)*) = self;
                 $($T.digest::<ByteOrder, Writer>(writer);)*
             }
             #[inline(always)]
             fn digest_native<Writer: DigestWriter>(&self, writer: &mut Writer) {
-                let ($($T),*) = self;
+                let ($($T,)*) = self;
                 $($T.digest_native::<Writer>(writer);)*
             }
         }
     };
 }
+tuple_configs!(A);
 tuple_configs!(A, B);
 tuple_configs!(A, B, C);
 tuple_configs!(A, B, C, D);
 tuple_configs!(A, B, C, D, E);
 tuple_configs!(A, B, C, D, E, F);
+tuple_configs!(A, B, C, D, E, F, G);
+tuple_configs!(A, B, C, D, E, F, G, H);
+tuple_configs!(A, B, C, D, E, F, G, H, I);
+tuple_configs!(A, B, C, D, E, F, G, H, I, J);
+tuple_configs!(A, B, C, D, E, F, G, H, I, J, K);
+tuple_configs!(A, B, C, D, E, F, G, H, I, J, K, L);
+tuple_configs!(A, B, C, D, E, F, G, H, I, J, K, L, M);
+tuple_configs!(A, B, C, D, E, F, G, H, I, J, K, L, M, N);
+tuple_configs!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O);
+tuple_configs!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P);
+
+#[cfg(test)]
+mod tests {
+    use crate::Digestible;
+    use byteorder::LittleEndian;
+
+    #[test]
+    fn tuples() {
+        let mut digest = Vec::new();
+        (1u8,).digest::<LittleEndian, _>(&mut digest);
+        assert_eq!(digest, vec![1]);
+
+        let mut digest = Vec::new();
+        (
+            1u8, 2u8, 3u8, 4u8, 5u8, 6u8, 7u8, 8u8, 9u8, 10u8, 11u8, 12u8, 13u8, 14u8, 15u8, 16u8,
+        )
+            .digest::<LittleEndian, _>(&mut digest);
+        assert_eq!(digest, (1..=16).collect::<Vec<u8>>());
+    }
+}
@@ -0,0 +1,230 @@
+/*!
+# Encoding Formats
+
+The bytes written by [Digestible] are versioned. Digests that are stored should pin a format.
+
+The format is selected by the [DigestWriter]. See [DigestWriter::FORMAT].
+Wrap a writer in [FormatWriter] or use [Digester::digest_v](crate::Digester::digest_v) to pick one.
+
+- [Legacy] is the default. It is the encoding of previous releases with the changes of 0.2.3.
+  Such as sorted `HashMap` and `HashSet` entries and the new encodings of paths and chrono types.
+  See the changelog. Digests of those types from 0.2.2 and earlier do not match.
+  Values are written one after another without any framing. So different values can have the same bytes.
+  Legacy will not change anymore after 0.2.3.
+- [FormatV1] frames every variable length value and tags every optional value.
+  Two different values of the same type never have the same bytes.
+
+All numbers are written with the [ByteOrder] passed to [Digestible::digest].
+
+## Format V1 Specification
+`len` is the number of elements or bytes written as a u64.
+
+| Type                                                         | Encoding                                                                  |
+|--------------------------------------------------------------|---------------------------------------------------------------------------|
+| `u8`..`u128`, `i8`..`i128`                                   | Fixed width integer                                                       |
+| `usize`, `isize`                                             | Written as a u64 or i64 on every platform                                 |
+| `f32`, `f64`                                                 | IEEE 754 bits. Every NaN is written as the canonical quiet NaN            |
+| `bool`                                                       | u8 `0` or `1`                                                             |
+| `char`                                                       | The unicode scalar value as a u32                                         |
+| `str`, `String`, `CStr`, `CString`, `OsStr`, `Bytes`         | `len` then the bytes                                                      |
+| `Path`, `PathBuf`                                            | `len` then the normalized path bytes. See [Path](std::path::Path)         |
+| `Option`                                                     | u8 `0` for None. u8 `1` followed by the value for Some                    |
+| `Result`                                                     | u8 `0` followed by the Ok value. u8 `1` followed by the Err value         |
+| `[T]`, `Vec`, `VecDeque`, `LinkedList`, `BTreeSet`           | `len` then each element in order                                          |
+| `BTreeMap`                                                   | `len` then each key followed by its value in order                        |
+| `HashMap`, `HashSet`, `BinaryHeap`                           | `len` then each entry sorted by its encoded bytes                         |
+| `Range`, `RangeFrom`, `RangeTo`                              | The start then the end. Only the bound that is set                        |
+| `RangeInclusive`, `RangeToInclusive`                         | u8 `1` then the same as `Range` and `RangeTo`                             |
+| Tuples                                                       | Each element one after another                                            |
+| `Duration`                                                   | The seconds as a u64 then the subsecond nanoseconds as a u32              |
+| `Box`, `Rc`, `Arc`, `Cow`, `&T`, `Cell`, `RefCell`, `Mutex`  | The inner value                                                           |
+| `rc::Weak`, `sync::Weak`                                     | The upgraded value as an `Option`                                         |
+| `serde_json::Value`                                          | [Tagged](crate::digest_with::json::digest_tagged)                         |
+| Readers and files                                            | Chunks of up to 8192 bytes. Each chunk is `len` then the bytes. Ended by a `len` of `0` |
+
+Every other type is written the same as in Legacy. Those encodings already have a fixed size or a tag.
+
+### Derived Types
+- The type header is `len` then the [type_name](core::any::type_name).
+- Enums write the type header then `len` and the variant name.
+- Fields are written one after another.
+*/
+use crate::{DigestWriter, Digestible};
+use byteorder::ByteOrder;
+use core::marker::PhantomData;
+
+/// The encoding format used by a [DigestWriter]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Format {
+    /// See [Legacy]
+    #[default]
+    Legacy,
+    /// See [FormatV1]
+    V1,
+}
+/// A type level [Format]. Used with [FormatWriter] and [Digester::digest_v](crate::Digester::digest_v)
+pub trait EncodingFormat {
+    /// The format
+    const FORMAT: Format;
+}
+/// The encoding used before formats were versioned. See [module docs](self)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Legacy;
+impl EncodingFormat for Legacy {
+    const FORMAT: Format = Format::Legacy;
+}
+/// Version 1 of the encoding. See [module docs](self)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct FormatV1;
+impl EncodingFormat for FormatV1 {
+    const FORMAT: Format = Format::V1;
+}
+
+/// Wraps a [DigestWriter] and selects the format `F`
+#[derive(Debug, Clone, Default)]
+pub struct FormatWriter<F: EncodingFormat, W: DigestWriter> {
+    writer: W,
+    _format: PhantomData<F>,
+}
+impl<F: EncodingFormat, W: DigestWriter> FormatWriter<F, W> {
+    /// Wraps the writer
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            _format: PhantomData,
+        }
+    }
+    /// Returns the inner writer
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+/// Forwards the methods of [DigestWriter] to the `writer` field
+macro_rules! forward_to_writer {
+    ($($fn_name:ident($data:ty)),*) => {
+        $(
+            #[inline(always)]
+            fn $fn_name(&mut self, data: $data) {
+                self.writer.$fn_name(data)
+            }
+        )*
+    };
+    ($(ByteOrder $fn_name:ident($data:ty)),*) => {
+        $(
+            #[inline(always)]
+            fn $fn_name<B: ByteOrder>(&mut self, data: $data) {
+                self.writer.$fn_name::<B>(data)
+            }
+        )*
+    };
+}
+pub(crate) use forward_to_writer;
+impl<F: EncodingFormat, W: DigestWriter> DigestWriter for FormatWriter<F, W> {
+    const FORMAT: Format = F::FORMAT;
+    const TRACES_PATHS: bool = W::TRACES_PATHS;
+    forward_to_writer!(
+        write(&[u8]),
+        write_bool(bool),
+        write_u8(u8),
+        write_i8(i8),
+        write_str(&str)
+    );
+    forward_to_writer!(
+        ByteOrder write_usize(usize),
+        ByteOrder write_isize(isize),
+        ByteOrder write_u16(u16),
+        ByteOrder write_u32(u32),
+        ByteOrder write_u64(u64),
+        ByteOrder write_u128(u128),
+        ByteOrder write_i16(i16),
+        ByteOrder write_i32(i32),
+        ByteOrder write_i64(i64),
+        ByteOrder write_i128(i128),
+        ByteOrder write_f32(f32),
+        ByteOrder write_f64(f64)
+    );
+    forward_to_writer!(enter_field(&str), enter_index(usize));
+    #[inline(always)]
+    fn enter_key<K: Digestible + ?Sized>(&mut self, key: &K) {
+        self.writer.enter_key(key)
+    }
+    #[inline(always)]
+    fn exit(&mut self) {
+        self.writer.exit()
+    }
+    #[inline(always)]
+    fn digest_key(&self) -> Option<&[u8]> {
+        self.writer.digest_key()
+    }
+}
+
+/// Digests the inner value with the format `F`. Used by [Digester::digest_v](crate::Digester::digest_v)
+pub(crate) struct WithFormat<'a, F: EncodingFormat, D: Digestible + ?Sized>(
+    pub(crate) &'a D,
+    pub(crate) PhantomData<F>,
+);
+impl<F: EncodingFormat, D: Digestible + ?Sized> Digestible for WithFormat<'_, F, D> {
+    fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
+        self.0
+            .digest::<B, _>(&mut FormatWriter::<F, &mut W>::new(writer));
+    }
+}
+
+/// If the writer uses [FormatV1]
+#[inline(always)]
+pub(crate) const fn is_v1<W: DigestWriter + ?Sized>() -> bool {
+    matches!(W::FORMAT, Format::V1)
+}
+/// Writes the length as a u64 in [FormatV1]. Nothing in Legacy
+#[inline(always)]
+pub(crate) fn write_len<B: ByteOrder, W: DigestWriter + ?Sized>(writer: &mut W, len: usize) {
+    if is_v1::<W>() {
+        writer.write_u64::<B>(len as u64);
+    }
+}
+/// Writes the bytes with a length prefix in [FormatV1]. Only the bytes in Legacy
+#[inline(always)]
+pub(crate) fn write_bytes<B: ByteOrder, W: DigestWriter + ?Sized>(writer: &mut W, bytes: &[u8]) {
+    write_len::<B, W>(writer, bytes.len());
+    writer.write(bytes);
+}
+/// Writes the u8 tag in [FormatV1]. Nothing in Legacy
+#[inline(always)]
+pub(crate) fn write_tag<W: DigestWriter + ?Sized>(writer: &mut W, tag: u8) {
+    if is_v1::<W>() {
+        writer.write_u8(tag);
+    }
+}
+
+/// A buffer that uses the same format and [key](DigestWriter::digest_key) as `W`.
+///
+/// Used to digest values before sorting them
+#[cfg(feature = "alloc")]
+pub(crate) struct FormatBuffer<'k, W: ?Sized> {
+    pub(crate) buffer: alloc::vec::Vec<u8>,
+    key: Option<&'k [u8]>,
+    _writer: PhantomData<fn() -> *const W>,
+}
+#[cfg(feature = "alloc")]
+impl<'k, W: DigestWriter + ?Sized> FormatBuffer<'k, W> {
+    /// The key should be the key of the parent writer
+    pub(crate) fn new(key: Option<&'k [u8]>) -> Self {
+        Self {
+            buffer: alloc::vec::Vec::new(),
+            key,
+            _writer: PhantomData,
+        }
+    }
+}
+#[cfg(feature = "alloc")]
+impl<W: DigestWriter + ?Sized> DigestWriter for FormatBuffer<'_, W> {
+    const FORMAT: Format = W::FORMAT;
+    #[inline(always)]
+    fn write(&mut self, data: &[u8]) {
+        self.buffer.extend_from_slice(data);
+    }
+    #[inline(always)]
+    fn digest_key(&self) -> Option<&[u8]> {
+        self.key
+    }
+}
@@ -1,6 +1,8 @@
+use crate::sha256::Sha256Hasher;
 use crate::{DigestWriter, Digester, Digestible};
-use byteorder::ByteOrder;
-use core::hash::Hasher;
+use byteorder::{ByteOrder, NativeEndian};
+use core::hash::{BuildHasher, Hasher};
+use core::marker::PhantomData;
 macro_rules! map_to_hasher {
     ( $(($call:ident($call_param:ident: $call_type:ty) => $to:ident)),*) => {
         $(
@@ -67,6 +69,8 @@ impl<H: Hasher> Digester for DigesterUsingHasher<'_, H> {
 }
 
 /// Foreign Types that do not implement Digestible and do not have a way to access the inner data
+///
+/// Integers written by the [Hash] implementation are written with the ByteOrder `B`.
 /// ## Example
 /// ```rust
 /// use byteorder::ByteOrder;
@@ -80,7 +84,7 @@ impl<H: Hasher> Digester for DigesterUsingHasher<'_, H> {
 /// pub struct MyHashableType(u32);
 /// impl Digestible for MyHashableType {
 ///     fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
-///         let mut hashable_hack = HashableHack(writer);
+///         let mut hashable_hack = HashableHack::<_, B>::new(writer);
 ///         <Self as Hash>::hash(self, &mut hashable_hack);
 ///     }
 /// }
@@ -91,20 +95,30 @@ impl<H: Hasher> Digester for DigesterUsingHasher<'_, H> {
 /// let result = hasher.digest::<NativeEndian>(&test).to_vec();
 /// println!("{:?}", result);
 /// ```
-pub struct HashableHack<'w, W: crate::DigestWriter>(pub &'w mut W);
+pub struct HashableHack<'w, W: crate::DigestWriter, B: ByteOrder = NativeEndian>(
+    pub &'w mut W,
+    PhantomData<B>,
+);
+impl<'w, W: crate::DigestWriter, B: ByteOrder> HashableHack<'w, W, B> {
+    /// Creates a new HashableHack that writes integers with the ByteOrder `B`
+    #[inline(always)]
+    pub fn new(writer: &'w mut W) -> Self {
+        Self(writer, PhantomData)
+    }
+}
 
 macro_rules! map_to_digester {
     ( $(($call:ident($call_param:ident: $call_type:ty) => $to:ident)),*) => {
         $(
             #[inline(always)]
             fn $call(&mut self, $call_param: $call_type) {
-                self.0.$to::<byteorder::NativeEndian>($call_param)
+                self.0.$to::<B>($call_param)
             }
         )*
     };
 
 }
-impl<W: crate::DigestWriter> Hasher for HashableHack<'_, W> {
+impl<W: crate::DigestWriter, B: ByteOrder> Hasher for HashableHack<'_, W, B> {
     /// Does nothing.
     fn finish(&self) -> u64 {
         0
@@ -127,3 +141,74 @@ impl<W: crate::DigestWriter> Hasher for HashableHack<'_, W> {
         (write_isize(value: isize) => write_isize)
     );
 }
+
+/// A [BuildHasher] that creates [DigestHasher]s.
+///
+/// The hashes do not depend on a random seed, the platform or the process.
+/// So the output of `#[digestible(hash)]` can be stored or compared between machines.
+///
+/// Do not use it for keys chosen by an attacker.
+/// The hash has no secret key. So keys that land in the same bucket can be found ahead of time.
+/// No matter which hash function is used. The default hasher of [HashMap](std::collections::HashMap) uses a random key against this.
+///
+/// It is also slow. Every hash runs SHA-256. Without the `sha2` feature a fallback implementation is used that processes one byte at a time.
+///
+/// ## Example
+/// ```rust
+/// use digestible::hash_digester::DigestBuildHasher;
+/// use digestible::Digestible;
+/// use std::collections::HashMap;
+/// use std::hash::BuildHasher;
+/// #[derive(Digestible, PartialEq, Eq)]
+/// #[digestible(hash = LittleEndian)]
+/// pub struct Key {
+///     pub id: u32,
+/// }
+/// let mut map: HashMap<Key, &str, DigestBuildHasher> = HashMap::default();
+/// map.insert(Key { id: 1 }, "one");
+/// assert_eq!(map.get(&Key { id: 1 }), Some(&"one"));
+///
+/// let hash = DigestBuildHasher.hash_one(&Key { id: 1 });
+/// assert_eq!(hash, DigestBuildHasher.hash_one(&Key { id: 1 }));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DigestBuildHasher;
+impl BuildHasher for DigestBuildHasher {
+    type Hasher = DigestHasher;
+    fn build_hasher(&self) -> DigestHasher {
+        DigestHasher::default()
+    }
+}
+/// A deterministic [Hasher] using SHA-256.
+///
+/// Integers are written as little endian. `usize` and `isize` are written as 64 bit integers.
+/// [finish](Hasher::finish) returns the first 8 bytes of the SHA-256 hash as a little endian u64.
+#[derive(Debug, Clone, Default)]
+pub struct DigestHasher(Sha256Hasher);
+macro_rules! write_le {
+    ( $(($call:ident($call_param:ident: $call_type:ty) as $as:ty)),*) => {
+        $(
+            #[inline(always)]
+            fn $call(&mut self, $call_param: $call_type) {
+                self.write(&($call_param as $as).to_le_bytes())
+            }
+        )*
+    };
+}
+impl Hasher for DigestHasher {
+    fn finish(&self) -> u64 {
+        let hash = self.0.clone().finalize();
+        u64::from_le_bytes(hash[..8].try_into().expect("hash is 32 bytes"))
+    }
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+    write_le!(
+        (write_u16(value: u16) as u16), (write_u32(value: u32) as u32),
+        (write_u64(value: u64) as u64), (write_u128(value: u128) as u128),
+        (write_usize(value: usize) as u64),
+        (write_i16(value: i16) as i16), (write_i32(value: i32) as i32),
+        (write_i64(value: i64) as i64), (write_i128(value: i128) as i128),
+        (write_isize(value: isize) as i64)
+    );
+}
@@ -0,0 +1,237 @@
+/*!
+# Digesting Readers and Files
+
+Streams the bytes of a [Read] into a [DigestWriter] without loading everything into memory.
+
+Legacy writes the bytes as is. The same as [digest_as_bytes](crate::digest_with::digest_as_bytes).
+
+[FormatV1](crate::format::FormatV1) splits the bytes into chunks of 8192 bytes.
+Each chunk is written as its length as a u64 followed by the bytes. The last chunk can be shorter.
+The end is marked by a length of `0`.
+So the bytes do not depend on how much the reader returns from each read.
+
+## Example
+```rust,no_run
+use digestible::io::FileContents;
+use digestible::{Digestible, Digester};
+use sha2::{Digest, Sha256};
+#[derive(Digestible)]
+pub struct Artifact {
+    pub name: String,
+    pub contents: FileContents,
+}
+let artifact = Artifact {
+    name: "app".to_string(),
+    contents: FileContents::new("target/release/app"),
+};
+let result = Sha256::new().digest_native(&artifact);
+```
+*/
+use crate::format::is_v1;
+use crate::{DigestWriter, Digestible};
+use byteorder::{ByteOrder, NativeEndian};
+use std::cell::RefCell;
+use std::fs::File;
+use std::io::{ErrorKind, Read};
+use std::path::{Path, PathBuf};
+
+/// The size of the buffer used to read
+const BUFFER_SIZE: usize = 8 * 1024;
+
+/// Writes every byte of the reader into the writer.
+///
+/// Returns the number of bytes written
+pub fn digest_reader<R: Read + ?Sized, W: DigestWriter>(
+    reader: &mut R,
+    writer: &mut W,
+) -> std::io::Result<u64> {
+    let mut buffer = [0u8; BUFFER_SIZE];
+    let mut total = 0u64;
+    loop {
+        let read = match reader.read(&mut buffer) {
+            Ok(0) => return Ok(total),
+            Ok(read) => read,
+            Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+            Err(error) => return Err(error),
+        };
+        writer.write(&buffer[..read]);
+        total += read as u64;
+    }
+}
+
+/// Writes the reader in chunks of [BUFFER_SIZE]. Each chunk is prefixed with its length.
+/// Ends with a length of `0`
+fn digest_reader_chunked<B: ByteOrder, R: Read + ?Sized, W: DigestWriter>(
+    reader: &mut R,
+    writer: &mut W,
+) -> std::io::Result<u64> {
+    let mut buffer = [0u8; BUFFER_SIZE];
+    let mut total = 0u64;
+    loop {
+        let mut filled = 0;
+        while filled < BUFFER_SIZE {
+            match reader.read(&mut buffer[filled..]) {
+                Ok(0) => break,
+                Ok(read) => filled += read,
+                Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+                Err(error) => return Err(error),
+            }
+        }
+        writer.write_u64::<B>(filled as u64);
+        if filled == 0 {
+            return Ok(total);
+        }
+        writer.write(&buffer[..filled]);
+        total += filled as u64;
+    }
+}
+/// Writes the reader using the format of the writer
+fn digest_reader_formatted<B: ByteOrder, R: Read + ?Sized, W: DigestWriter>(
+    reader: &mut R,
+    writer: &mut W,
+) -> std::io::Result<u64> {
+    if is_v1::<W>() {
+        digest_reader_chunked::<B, R, W>(reader, writer)
+    } else {
+        digest_reader(reader, writer)
+    }
+}
+
+/// Digests the remaining bytes of a reader.
+///
+/// The reader is consumed by digesting. So digesting twice will not give the same result
+/// unless the reader is rewound.
+///
+/// # Panics
+/// When digested if the reader returns an error. Use [ReadDigest::try_digest] to handle the error
+#[derive(Debug)]
+pub struct ReadDigest<R: Read>(RefCell<R>);
+impl<R: Read> ReadDigest<R> {
+    /// Wraps the reader
+    pub fn new(reader: R) -> Self {
+        Self(RefCell::new(reader))
+    }
+    /// Returns the reader
+    pub fn into_inner(self) -> R {
+        self.0.into_inner()
+    }
+    /// Writes the remaining bytes of the reader. Returns the number of bytes read
+    pub fn try_digest<W: DigestWriter>(&self, writer: &mut W) -> std::io::Result<u64> {
+        self.try_digest_with_order::<NativeEndian, W>(writer)
+    }
+    /// Same as [try_digest](Self::try_digest). Chunk lengths in V1 are written with the ByteOrder `B`
+    pub fn try_digest_with_order<B: ByteOrder, W: DigestWriter>(
+        &self,
+        writer: &mut W,
+    ) -> std::io::Result<u64> {
+        digest_reader_formatted::<B, R, W>(&mut *self.0.borrow_mut(), writer)
+    }
+}
+impl<R: Read> Digestible for ReadDigest<R> {
+    fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
+        if let Err(error) = self.try_digest_with_order::<B, W>(writer) {
+            panic!("Failed to read data for digest: {error}");
+        }
+    }
+}
+
+/// The contents of a file.
+///
+/// The file is opened and streamed every time it is digested.
+///
+/// # Panics
+/// When digested if the file can not be opened or read.
+/// Use [FileContents::try_digest] to handle the error
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FileContents(pub PathBuf);
+impl FileContents {
+    /// Creates a new FileContents for the path
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self(path.into())
+    }
+    /// The path of the file
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+    /// Writes the contents of the file. Returns the number of bytes read
+    pub fn try_digest<W: DigestWriter>(&self, writer: &mut W) -> std::io::Result<u64> {
+        self.try_digest_with_order::<NativeEndian, W>(writer)
+    }
+    /// Same as [try_digest](Self::try_digest). Chunk lengths in V1 are written with the ByteOrder `B`
+    pub fn try_digest_with_order<B: ByteOrder, W: DigestWriter>(
+        &self,
+        writer: &mut W,
+    ) -> std::io::Result<u64> {
+        let mut file = File::open(&self.0)?;
+        digest_reader_formatted::<B, File, W>(&mut file, writer)
+    }
+}
+impl Digestible for FileContents {
+    fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
+        if let Err(error) = self.try_digest_with_order::<B, W>(writer) {
+            panic!("Failed to read {} for digest: {error}", self.0.display());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{digest_reader, FileContents, ReadDigest};
+    use crate::format::{FormatV1, FormatWriter};
+    use crate::Digestible;
+    use byteorder::LittleEndian;
+    use std::io::Cursor;
+
+    #[test]
+    fn reader() {
+        let data: Vec<u8> = (0..20_000u32).map(|v| v as u8).collect();
+        let mut digest = Vec::new();
+        let read = digest_reader(&mut Cursor::new(&data), &mut digest).unwrap();
+        assert_eq!(read, data.len() as u64);
+        assert_eq!(digest, data);
+
+        let mut digest = Vec::new();
+        ReadDigest::new(Cursor::new(&data)).digest::<LittleEndian, _>(&mut digest);
+        assert_eq!(digest, data);
+    }
+
+    /// A reader that returns one byte per read
+    struct Slow<'a>(&'a [u8]);
+    impl std::io::Read for Slow<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let Some((first, rest)) = self.0.split_first() else {
+                return Ok(0);
+            };
+            buf[0] = *first;
+            self.0 = rest;
+            Ok(1)
+        }
+    }
+
+    #[test]
+    fn reader_v1() {
+        let data: Vec<u8> = (0..10_000u32).map(|v| v as u8).collect();
+        let mut digest = FormatWriter::<FormatV1, _>::new(Vec::new());
+        ReadDigest::new(Slow(&data)).digest::<LittleEndian, _>(&mut digest);
+        let mut expected = 8192u64.to_le_bytes().to_vec();
+        expected.extend_from_slice(&data[..8192]);
+        expected.extend_from_slice(&1808u64.to_le_bytes());
+        expected.extend_from_slice(&data[8192..]);
+        expected.extend_from_slice(&0u64.to_le_bytes());
+        assert_eq!(digest.into_inner(), expected);
+    }
+
+    #[test]
+    fn file() {
+        let path = std::env::temp_dir().join(format!("digestible-io-{}", std::process::id()));
+        std::fs::write(&path, b"file contents").unwrap();
+        let mut digest = Vec::new();
+        FileContents::new(&path).digest::<LittleEndian, _>(&mut digest);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(digest, b"file contents");
+
+        assert!(FileContents::new(&path)
+            .try_digest(&mut Vec::new())
+            .is_err());
+    }
+}
@@ -0,0 +1,123 @@
+/*!
+# Keyed and Redacted Fields
+
+Fields with personal data can still change the digest without being guessable from it.
+
+## Field Attributes
+- `#[digestible(salted)]` writes the HMAC-SHA256 of the field with the secret key. The name of the field is the context
+- `#[digestible(keyed = "context")]` is the same as `salted` with the given context.
+  Fields with the same context and value have the same HMAC. Even in different types
+- `#[digestible(redact)]` only writes a presence marker `u8`.
+  `1` for `Some` and `0` for `None` if the type of the field is `Option<...>`. Always `1` for other types
+
+## HMAC
+The input of the HMAC is the length of the context as a u64 in little endian, the context and the field.
+The field is digested with the same ByteOrder and [format](crate::format) as the rest of the value.
+The 32 bytes of the HMAC are written into the writer.
+
+## The Secret Key
+The key is given while digesting with [KeyedWriter] or [Digester::digest_keyed](crate::Digester::digest_keyed).
+Without a key `salted` and `keyed` fields are written the same as `redact` fields.
+So the field never ends up in the digest without the key.
+
+Collections that digest their items into a buffer or hash first, such as `HashSet` or
+[unordered](crate::digest_with::unordered), give the key to their items.
+[Cached](crate::cached::Cached) digests the value again with the key.
+[ByDigest](crate::by_digest::ByDigest) does not. Its digest is computed without a key
+
+## Example
+```rust
+use digestible::{Digester, Digestible};
+use byteorder::LittleEndian;
+use sha2::{Digest, Sha256};
+#[derive(Digestible)]
+pub struct User {
+    pub id: u32,
+    #[digestible(keyed = "email")]
+    pub email: String,
+    #[digestible(redact)]
+    pub phone: Option<String>,
+}
+let user = User {
+    id: 1,
+    email: "user@example.com".to_string(),
+    phone: None,
+};
+let pepper = b"a secret pepper";
+let published = Sha256::new().digest_keyed::<LittleEndian>(pepper, &user);
+// A different key gives a different digest
+assert_ne!(published, Sha256::new().digest_keyed::<LittleEndian>(b"guess", &user));
+```
+*/
+use crate::format::{forward_to_writer, Format};
+use crate::{DigestWriter, Digestible};
+use byteorder::ByteOrder;
+
+/// Gives the secret key of `salted` and `keyed` fields to the writer. See [module docs](self)
+///
+/// Everything else is passed to the inner writer
+pub struct KeyedWriter<'k, W> {
+    writer: W,
+    key: &'k [u8],
+}
+impl<'k, W: DigestWriter> KeyedWriter<'k, W> {
+    /// Wraps the writer
+    pub fn new(writer: W, key: &'k [u8]) -> Self {
+        Self { writer, key }
+    }
+    /// Returns the inner writer
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+impl<W: DigestWriter> DigestWriter for KeyedWriter<'_, W> {
+    const FORMAT: Format = W::FORMAT;
+    const TRACES_PATHS: bool = W::TRACES_PATHS;
+    forward_to_writer!(
+        write(&[u8]),
+        write_bool(bool),
+        write_u8(u8),
+        write_i8(i8),
+        write_str(&str),
+        enter_field(&str),
+        enter_index(usize)
+    );
+    forward_to_writer!(
+        ByteOrder write_usize(usize),
+        ByteOrder write_isize(isize),
+        ByteOrder write_u16(u16),
+        ByteOrder write_u32(u32),
+        ByteOrder write_u64(u64),
+        ByteOrder write_u128(u128),
+        ByteOrder write_i16(i16),
+        ByteOrder write_i32(i32),
+        ByteOrder write_i64(i64),
+        ByteOrder write_i128(i128),
+        ByteOrder write_f32(f32),
+        ByteOrder write_f64(f64)
+    );
+    #[inline(always)]
+    fn enter_key<K: Digestible + ?Sized>(&mut self, key: &K) {
+        self.writer.enter_key(key)
+    }
+    #[inline(always)]
+    fn exit(&mut self) {
+        self.writer.exit()
+    }
+    #[inline(always)]
+    fn digest_key(&self) -> Option<&[u8]> {
+        Some(self.key)
+    }
+}
+
+/// Digests the inner value with the key. Used by [Digester::digest_keyed](crate::Digester::digest_keyed)
+pub(crate) struct WithKey<'a, D: Digestible + ?Sized> {
+    pub(crate) key: &'a [u8],
+    pub(crate) value: &'a D,
+}
+impl<D: Digestible + ?Sized> Digestible for WithKey<'_, D> {
+    fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
+        self.value
+            .digest::<B, _>(&mut KeyedWriter::new(writer, self.key));
+    }
+}
@@ -12,7 +12,7 @@ A more dynamic [Hash](core::hash::Hash) and [Hasher](core::hash::Hasher) trait f
   or any type the Digester uses.
 - Skip Fields with `#[digestible(skip)]`
 - 'digest_with' and 'with' to override the default digest behavior.
-[digest_with](https://docs.rs/digestible/0.2.0/digestible/digest_with/index.html)
+  [digest_with](https://docs.rs/digestible/0.2.0/digestible/digest_with/index.html)
 - Support for all Hashing Algorithms that implement [digest::Digest] such as SHA2, md-5, and many more.
 - Writing Type Headers to prevent collisions with similar types. (This is optional and can be disabled with `#[digestible(type_header = none)]`)
 ---
@@ -21,6 +21,10 @@ A more dynamic [Hash](core::hash::Hash) and [Hasher](core::hash::Hasher) trait f
 - `no_std` Support
 - Digest to implement Digester for all types that implement [digest::Digest](https://docs.rs/digest/latest/digest/)
 - Float and Atomic Support using `digest_with`
+- `cas` Content addressable storage keyed by digests. [cas](crate::cas)
+- `signature` Signing digests with the `signature` crate. [signed](crate::signed)
+- `sha2` Uses the `sha2` crate for the SHA-256 hashing done at runtime. Such as [multiset](crate::multiset) hashes, [keyed](crate::keyed) fields and [DigestHasher](crate::hash_digester::DigestHasher). The output is the same
+- `multihash` Multihash and CIDv1 targets with codes for the `sha2`, `sha3`, `blake2` and `blake3` features. [multihash](crate::multihash)
 #### [Digestible](digestible::Digestible)
 A trait that allows you to digest data into a [Digester](digester::Digester)
 Equivalent to [Hash](core::hash::Hash) but with more control over the digesting process
@@ -68,6 +72,28 @@ pub use byteorder;
 /// Provides some sometimes useful digest_with implementations
 pub mod digest_with;
 
+pub mod by_digest;
+pub mod cached;
+#[cfg(feature = "cas")]
+pub mod cas;
+#[cfg(feature = "alloc")]
+pub mod diff;
+pub mod format;
+#[cfg(feature = "std")]
+pub mod io;
+pub mod keyed;
+#[cfg(all(feature = "digest_0_10", feature = "alloc"))]
+pub mod merkle;
+#[cfg(feature = "multihash")]
+pub mod multihash;
+pub mod multiset;
+pub mod schema;
+#[cfg(feature = "serde")]
+pub mod serde_digest;
+mod sha256;
+#[cfg(feature = "signature")]
+pub mod signed;
+
 #[doc(hidden)]
 pub mod _private;
 /// Provides inter-compatibility with [Hasher](core::hash::Hasher)/[Hash](core::hash::Hash) and [Digester](crate::Digester)/[Digestible](crate::Digestible)
@@ -84,6 +110,9 @@ pub use digester_writer::DigestWriter;
 ///
 /// No padding or spaces are added. Similar to how [Hash](core::hash::Hash) works.
 ///
+/// Every field is digested between [enter_field](DigestWriter::enter_field) and [exit](DigestWriter::exit).
+/// The hooks do not change the digest. They are used by [diff](crate::diff)
+///
 ///
 /// ## Container Attributes
 /// ### type_header
@@ -91,21 +120,22 @@ pub use digester_writer::DigestWriter;
 /// Options:
 /// - none: No type header is written `#[digestible(type_header = none)]`
 /// - HashName: The name of the hash is written as the type header (Default) `#[digestible(type_header = HashName)]`
-/// ### impl_hash
+/// ### hash
 /// The macro will also implement [Hash](core::hash::Hash) for the given struct or enum using [DigesterUsingHasher](hash_digester::DigesterUsingHasher).
 /// This will put the same data the Digestible trait would into the hasher. Allowing you to use `digest_with` and including type headers.
 /// This will not be useful if you are using this type in a HashMap or HashSet. As this will provide more data than the Hash trait would. and can be slower.
+/// Use [DigestBuildHasher](hash_digester::DigestBuildHasher) to get hashes that are the same on every machine.
 /// By default this uses NativeEndian.
 /// You can change this by using
-/// - `#[digestible(impl_hash = LittleEndian)]`
-/// - `#[digestible(impl_hash = BigEndian)]`
-/// - `#[digestible(impl_hash = NetworkEndian)]`
-/// - `#[digestible(impl_hash = NativeEndian)]`
+/// - `#[digestible(hash = LittleEndian)]`
+/// - `#[digestible(hash = BigEndian)]`
+/// - `#[digestible(hash = NetworkEndian)]`
+/// - `#[digestible(hash = NativeEndian)]`
 /// #### Output
 ///```rust
 /// use digestible_macros::Digestible;
 /// #[derive(Digestible)]
-/// // Add this #[digestible(impl_hash = LittleEndian)]
+/// // Add this #[digestible(hash = LittleEndian)]
 /// pub struct MyStruct {
 ///     pub id: u32,
 /// }
@@ -120,7 +150,77 @@ pub use digester_writer::DigestWriter;
 ///     }
 /// }
 /// ```
+/// ### transparent
+/// Digests a struct exactly like its one non skipped field. No type header is written.
+/// `#[digestible(transparent)]`
+/// ### into
+/// Clones the container and converts it into the given type with [Into] before digesting.
+/// Requires the container to implement [Clone]. `#[digestible(into = "OtherType")]`
+/// ### from_ref
+/// Converts a reference of the container into the given type with [From] before digesting.
+/// `#[digestible(from_ref = "OtherType")]` where `OtherType: From<&Self>`
+///
+/// Both `into` and `from_ref` digest only the other type. The type header is not written.
+/// ```rust
+/// use digestible::Digestible;
+/// use uuid::Uuid;
+/// #[derive(Digestible)]
+/// #[digestible(transparent)]
+/// pub struct UserId(Uuid);
+///
+/// #[derive(Digestible)]
+/// pub struct Point {
+///     pub x: i32,
+///     pub y: i32,
+/// }
+/// #[derive(Digestible)]
+/// #[digestible(from_ref = "Point")]
+/// pub struct PointArray([i32; 2]);
+/// impl From<&PointArray> for Point {
+///     fn from(value: &PointArray) -> Self {
+///         Point {
+///             x: value.0[0],
+///             y: value.0[1],
+///         }
+///     }
+/// }
+/// ```
+/// ### with
+/// Digests the whole container with the given function. Works for structs, enums and unions.
+/// Uses the same signature as the `with` field attribute. `#[digestible(with = my_digest_fn)]`
+/// ### bytes
+/// Writes the raw bytes of a `Copy` struct or union. [Read More](crate::digest_with::raw_bytes)
+///
+/// The container must be `#[repr(C)]`, `#[repr(transparent)]` or `#[repr(packed)]`
+/// and every field must implement [NoPadding](crate::digest_with::raw_bytes::NoPadding).
+/// Padding between fields and union fields smaller than the union are a compile error.
+/// `#[digestible(bytes)]`
+/// ```rust
+/// use digestible::Digestible;
+/// #[derive(Digestible, Clone, Copy)]
+/// #[digestible(bytes)]
+/// #[repr(C)]
+/// pub union IntOrFloat {
+///     pub int: u64,
+///     pub float: f64,
+/// }
+/// ```
+/// ### endian
+/// Pins the [ByteOrder](byteorder::ByteOrder) used for every field of the container.
+/// The byte order passed to [Digestible::digest] is ignored. `#[digestible(endian = BigEndian)]`
+/// ### crate
+/// Path to the digestible crate. Used when digestible is re-exported by another crate.
+/// `#[digestible(crate = "my_framework::digestible")]`
+/// ### schema
+/// Implements [DigestSchema](crate::schema::DigestSchema) for the container.
+/// Every field type must implement it too. [Read More](crate::schema)
+/// `#[digestible(schema)]`
+/// ## Variant Attributes
+/// ### skip
+/// Only the name of the variant is written. The fields of the variant are ignored.
+/// `#[digestible(skip)]`
 /// ## Field Attributes
+/// Field attributes work the same on struct fields and enum variant fields.
 /// ### skip: Skips the field when digesting
 /// ### with: Path to a digest fn
 /// Required Fn Signature: `fn digest<B: ByteOrder, W: DigestWriter>(digest: Type, writer: &mut W);`
@@ -139,7 +239,28 @@ pub use digester_writer::DigestWriter;
 /// Function provided in the [digest_with](crate::digest_with) module Example: `#[digestible(digest_with = digest_with_hash)]`
 /// ### as_ref
 /// Will call as_ref on the field before digesting it. Example: `#[digestible(as_ref = TargetType)]`
+/// ### endian
+/// Pins the [ByteOrder](byteorder::ByteOrder) used for the field and everything inside of it.
+/// Overrides the byte order of the container. Example: `#[digestible(endian = BigEndian)]`
+/// ```rust
+/// use digestible::Digestible;
+/// #[derive(Digestible)]
+/// pub struct Packet {
+///     #[digestible(endian = BigEndian)]
+///     pub port: u16,
+///     pub payload_len: u32,
+/// }
+/// ```
+/// ### redact
+/// Only writes a presence marker. `Option` fields write if they are `Some`. [Read More](crate::keyed)
+/// `#[digestible(redact)]`
+/// ### salted and keyed
+/// Writes the HMAC of the field with a secret key given while digesting. [Read More](crate::keyed)
+/// `salted` uses the name of the field as the context. `#[digestible(salted)]` or `#[digestible(keyed = "context")]`
 pub use digestible_macros::Digestible;
+#[cfg(feature = "multihash")]
+#[doc(inline)]
+pub use multihash::{IntoMultihash, ToMultihash};
 #[cfg(feature = "base64")]
 #[doc(inline)]
 pub use to_base64::{IntoBase64, ToBase64};
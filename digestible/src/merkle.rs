@@ -0,0 +1,270 @@
+/*!
+# Merkle Tree Digests
+
+Digests large collections by splitting them into chunks of a fixed number of elements.
+Each chunk is hashed independently. So chunks can be hashed in parallel with the `rayon` feature.
+
+## Tree
+- Leaf: `H(0x00 || chunk)`. Each element of the chunk is digested one after another.
+  The elements are digested with the [format](crate::format) `F` of the [MerkleDigester]. [FormatV1] by default.
+  With [Legacy](crate::format::Legacy) the chunks `["a", "bc"]` and `["ab", "c"]` would have the same leaf.
+- Node: `H(0x01 || left || right)`
+- A node without a right sibling is moved up to the next level unchanged.
+- An empty collection has a single leaf of an empty chunk.
+
+The prefixes keep a leaf from being mistaken for a node.
+The tree only depends on the elements and the chunk size.
+So the root is the same no matter how many threads are used.
+
+## Example
+```rust
+use digestible::merkle::MerkleDigester;
+use byteorder::LittleEndian;
+use sha2::Sha256;
+
+let items: Vec<u64> = (0..10_000).collect();
+let digester = MerkleDigester::<Sha256>::new(4096);
+let tree = digester.digest_slice::<LittleEndian, _>(&items);
+assert_eq!(tree.leaves().len(), 3);
+assert!(tree.verify_chunk::<LittleEndian, _>(&digester, 1, &items[4096..8192]));
+```
+*/
+use crate::digester::digest_0_10::DigestConsumerInner;
+use crate::format::{EncodingFormat, FormatV1, FormatWriter};
+use crate::Digestible;
+use alloc::vec::Vec;
+use byteorder::ByteOrder;
+use core::marker::PhantomData;
+use digest_0_10::{Digest, Output};
+
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+/// Builds [MerkleTree]s using the hasher `D` and the [format](crate::format) `F`
+#[derive(Debug, Clone)]
+pub struct MerkleDigester<D: Digest + Clone, F: EncodingFormat = FormatV1> {
+    hasher: D,
+    chunk_size: usize,
+    _format: PhantomData<fn() -> F>,
+}
+impl<D: Digest + Clone, F: EncodingFormat> MerkleDigester<D, F> {
+    /// Creates a MerkleDigester with `chunk_size` elements per chunk
+    ///
+    /// # Panics
+    /// If chunk_size is 0
+    pub fn new(chunk_size: usize) -> Self {
+        Self::with_hasher(D::new(), chunk_size)
+    }
+    /// Every hash starts from a clone of the given hasher
+    ///
+    /// # Panics
+    /// If chunk_size is 0
+    pub fn with_hasher(hasher: D, chunk_size: usize) -> Self {
+        assert!(chunk_size > 0, "chunk_size must be greater than 0");
+        Self {
+            hasher,
+            chunk_size,
+            _format: PhantomData,
+        }
+    }
+    /// The number of elements in each chunk
+    pub fn chunk_size(&self) -> usize {
+        self.chunk_size
+    }
+    /// Hashes one chunk of elements
+    pub fn digest_chunk<B: ByteOrder, T: Digestible>(&self, chunk: &[T]) -> Output<D> {
+        let mut hasher = self.hasher.clone();
+        hasher.update([LEAF_PREFIX]);
+        let mut writer = FormatWriter::<F, _>::new(DigestConsumerInner(&mut hasher));
+        for item in chunk {
+            item.digest::<B, _>(&mut writer);
+        }
+        hasher.finalize()
+    }
+    /// Hashes every chunk of the slice one after another and builds the tree
+    pub fn digest_slice<B: ByteOrder, T: Digestible>(&self, items: &[T]) -> MerkleTree<D, F> {
+        let leaves = if items.is_empty() {
+            alloc::vec![self.digest_chunk::<B, T>(items)]
+        } else {
+            items
+                .chunks(self.chunk_size)
+                .map(|chunk| self.digest_chunk::<B, T>(chunk))
+                .collect()
+        };
+        self.tree_from_leaves(leaves)
+    }
+    /// Hashes the chunks of the slice in parallel and builds the tree.
+    ///
+    /// The result is the same as [digest_slice](Self::digest_slice)
+    #[cfg(feature = "rayon")]
+    pub fn digest_slice_par<B: ByteOrder, T: Digestible + Sync>(
+        &self,
+        items: &[T],
+    ) -> MerkleTree<D, F>
+    where
+        D: Send + Sync,
+        Output<D>: Send,
+    {
+        use rayon::prelude::*;
+        let leaves = if items.is_empty() {
+            alloc::vec![self.digest_chunk::<B, T>(items)]
+        } else {
+            items
+                .par_chunks(self.chunk_size)
+                .map(|chunk| self.digest_chunk::<B, T>(chunk))
+                .collect()
+        };
+        self.tree_from_leaves(leaves)
+    }
+    /// Builds the tree from already hashed leaves.
+    ///
+    /// Use this to get a new root after replacing a leaf
+    ///
+    /// # Panics
+    /// If leaves is empty
+    pub fn tree_from_leaves(&self, leaves: Vec<Output<D>>) -> MerkleTree<D, F> {
+        let root = self.root_from_leaves(&leaves);
+        MerkleTree {
+            leaves,
+            root,
+            _format: PhantomData,
+        }
+    }
+    /// Calculates the root of the given leaves
+    ///
+    /// # Panics
+    /// If leaves is empty
+    pub fn root_from_leaves(&self, leaves: &[Output<D>]) -> Output<D> {
+        assert!(!leaves.is_empty(), "a merkle tree needs at least one leaf");
+        let mut level: Vec<Output<D>> = leaves.to_vec();
+        while level.len() > 1 {
+            level = level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => self.node(left, right),
+                    [single] => single.clone(),
+                    _ => unreachable!("chunks of 2"),
+                })
+                .collect();
+        }
+        level.remove(0)
+    }
+    fn node(&self, left: &Output<D>, right: &Output<D>) -> Output<D> {
+        let mut hasher = self.hasher.clone();
+        hasher.update([NODE_PREFIX]);
+        hasher.update(left);
+        hasher.update(right);
+        hasher.finalize()
+    }
+}
+
+/// The leaves and root of a Merkle Tree. Created by [MerkleDigester] with the format `F`
+#[derive(Debug, Clone)]
+pub struct MerkleTree<D: Digest, F: EncodingFormat = FormatV1> {
+    leaves: Vec<Output<D>>,
+    root: Output<D>,
+    _format: PhantomData<fn() -> F>,
+}
+impl<D: Digest, F: EncodingFormat> PartialEq for MerkleTree<D, F> {
+    fn eq(&self, other: &Self) -> bool {
+        self.root == other.root && self.leaves == other.leaves
+    }
+}
+impl<D: Digest, F: EncodingFormat> Eq for MerkleTree<D, F> {}
+impl<D: Digest + Clone, F: EncodingFormat> MerkleTree<D, F> {
+    /// The root hash of the tree
+    pub fn root(&self) -> &Output<D> {
+        &self.root
+    }
+    /// The hash of every chunk in order
+    pub fn leaves(&self) -> &[Output<D>] {
+        &self.leaves
+    }
+    /// Returns the leaves and root
+    pub fn into_parts(self) -> (Vec<Output<D>>, Output<D>) {
+        (self.leaves, self.root)
+    }
+    /// Checks that the chunk at `index` has the same hash as the stored leaf
+    pub fn verify_chunk<B: ByteOrder, T: Digestible>(
+        &self,
+        digester: &MerkleDigester<D, F>,
+        index: usize,
+        chunk: &[T],
+    ) -> bool {
+        self.leaves
+            .get(index)
+            .is_some_and(|leaf| *leaf == digester.digest_chunk::<B, T>(chunk))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MerkleDigester;
+    use crate::format::Legacy;
+    use byteorder::LittleEndian;
+    use sha2::{Digest, Sha256};
+
+    #[test]
+    fn tree() {
+        let items: Vec<u32> = (0..5).collect();
+        let digester = MerkleDigester::<Sha256>::new(2);
+        let tree = digester.digest_slice::<LittleEndian, _>(&items);
+        assert_eq!(tree.leaves().len(), 3);
+
+        let leaf = |chunk: &[u32]| {
+            let mut hasher = Sha256::new();
+            hasher.update([0]);
+            for item in chunk {
+                hasher.update(item.to_le_bytes());
+            }
+            hasher.finalize()
+        };
+        let node = |left: &[u8], right: &[u8]| {
+            let mut hasher = Sha256::new();
+            hasher.update([1]);
+            hasher.update(left);
+            hasher.update(right);
+            hasher.finalize()
+        };
+        let left = node(&leaf(&[0, 1]), &leaf(&[2, 3]));
+        let root = node(&left, &leaf(&[4]));
+        assert_eq!(tree.root(), &root);
+
+        assert!(tree.verify_chunk::<LittleEndian, _>(&digester, 2, &[4u32]));
+        assert!(!tree.verify_chunk::<LittleEndian, _>(&digester, 2, &[5u32]));
+        assert!(!tree.verify_chunk::<LittleEndian, _>(&digester, 3, &[4u32]));
+    }
+
+    #[test]
+    fn uses_format() {
+        let a = ["a", "bc"];
+        let b = ["ab", "c"];
+        let digester = MerkleDigester::<Sha256>::new(2);
+        assert_ne!(
+            digester.digest_chunk::<LittleEndian, _>(&a),
+            digester.digest_chunk::<LittleEndian, _>(&b)
+        );
+        let legacy = MerkleDigester::<Sha256, Legacy>::new(2);
+        assert_eq!(
+            legacy.digest_chunk::<LittleEndian, _>(&a),
+            legacy.digest_chunk::<LittleEndian, _>(&b)
+        );
+    }
+
+    #[test]
+    fn empty() {
+        let digester = MerkleDigester::<Sha256>::new(2);
+        let tree = digester.digest_slice::<LittleEndian, u8>(&[]);
+        assert_eq!(tree.root().as_slice(), Sha256::digest([0]).as_slice());
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel() {
+        let items: Vec<u64> = (0..100_000).collect();
+        let digester = MerkleDigester::<Sha256>::new(1000);
+        let sequential = digester.digest_slice::<LittleEndian, _>(&items);
+        let parallel = digester.digest_slice_par::<LittleEndian, _>(&items);
+        assert_eq!(sequential, parallel);
+    }
+}
@@ -0,0 +1,457 @@
+/*!
+# Multihash and CID
+
+[ToMultihash] wraps a [Digester] so its target is a [Multihash]. Requires the `multihash` feature.
+
+A multihash is the code of the hash algorithm as an unsigned varint, the length of the digest as an unsigned varint
+and the digest. See [multiformats](https://github.com/multiformats/multihash).
+[to_cid_v1](Multihash::to_cid_v1) turns it into a CIDv1 string for IPFS and other content addressed systems.
+
+## Algorithm Codes
+[MultihashCode] is implemented for the hashers of these crates when their feature is enabled
+- `sha2` sha2-224, sha2-256, sha2-384, sha2-512, sha2-512-224 and sha2-512-256
+- `sha3` sha3-224, sha3-256, sha3-384, sha3-512 and keccak-224, keccak-256, keccak-384, keccak-512
+- `blake2` blake2b-256, blake2b-512 and blake2s-256
+- `blake3` blake3
+
+Other Digesters can be used with [ToMultihash::with_code]
+
+## Example
+Requires the `sha2` feature
+```rust
+# #[cfg(feature = "sha2")]
+# {
+use digestible::multihash::{codec, IntoMultihash};
+use digestible::Digester;
+use byteorder::LittleEndian;
+use sha2::{Digest, Sha256};
+let multihash = Sha256::new()
+    .into_multihash()
+    .digest::<LittleEndian>(&"hello world");
+assert_eq!(multihash.code(), 0x12);
+assert_eq!(&multihash.as_bytes()[..2], &[0x12, 0x20]);
+assert_eq!(
+    multihash.to_cid_v1(codec::RAW),
+    "bafkreifzjut3te2nhyekklss27nh3k72ysco7y32koao5eei66wof36n5e"
+);
+# }
+```
+*/
+use crate::digester::Digester;
+use crate::Digestible;
+use alloc::string::String;
+use alloc::vec::Vec;
+use byteorder::ByteOrder;
+use core::fmt::{Debug, Display, Formatter};
+
+/// The multicodec code of a hash algorithm
+pub trait MultihashCode {
+    /// The code from the [multicodec table](https://github.com/multiformats/multicodec/blob/master/table.csv)
+    const MULTIHASH_CODE: u64;
+}
+#[cfg(any(
+    feature = "sha2",
+    feature = "sha3",
+    feature = "blake2",
+    feature = "blake3"
+))]
+macro_rules! multihash_code {
+    ($($hasher:ty => $code:literal),*) => {
+        $(
+            impl MultihashCode for $hasher {
+                const MULTIHASH_CODE: u64 = $code;
+            }
+        )*
+    };
+}
+#[cfg(feature = "sha2")]
+multihash_code!(
+    sha2::Sha224 => 0x1013,
+    sha2::Sha256 => 0x12,
+    sha2::Sha384 => 0x20,
+    sha2::Sha512 => 0x13,
+    sha2::Sha512_224 => 0x1014,
+    sha2::Sha512_256 => 0x1015
+);
+#[cfg(feature = "sha3")]
+multihash_code!(
+    sha3::Sha3_224 => 0x17,
+    sha3::Sha3_256 => 0x16,
+    sha3::Sha3_384 => 0x15,
+    sha3::Sha3_512 => 0x14,
+    sha3::Keccak224 => 0x1a,
+    sha3::Keccak256 => 0x1b,
+    sha3::Keccak384 => 0x1c,
+    sha3::Keccak512 => 0x1d
+);
+#[cfg(feature = "blake2")]
+multihash_code!(
+    blake2::Blake2b<blake2::digest::consts::U32> => 0xb220,
+    blake2::Blake2b512 => 0xb240,
+    blake2::Blake2s256 => 0xb260
+);
+#[cfg(feature = "blake3")]
+multihash_code!(blake3::Hasher => 0x1e);
+
+/// Common multicodec codes of the content of a CID
+pub mod codec {
+    /// Raw bytes
+    pub const RAW: u64 = 0x55;
+    /// MerkleDAG protobuf
+    pub const DAG_PB: u64 = 0x70;
+    /// MerkleDAG CBOR
+    pub const DAG_CBOR: u64 = 0x71;
+    /// MerkleDAG JSON
+    pub const DAG_JSON: u64 = 0x0129;
+}
+
+/// A digest with the code of its algorithm. See [module docs](self)
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Multihash {
+    code: u64,
+    /// The encoded multihash
+    bytes: Vec<u8>,
+    /// Where the digest starts in `bytes`
+    digest_start: usize,
+}
+impl Multihash {
+    /// Creates a multihash from the code and the digest
+    pub fn new(code: u64, digest: &[u8]) -> Self {
+        let mut bytes = Vec::with_capacity(digest.len() + 12);
+        write_varint(&mut bytes, code);
+        write_varint(&mut bytes, digest.len() as u64);
+        let digest_start = bytes.len();
+        bytes.extend_from_slice(digest);
+        Self {
+            code,
+            bytes,
+            digest_start,
+        }
+    }
+    /// Reads an encoded multihash. All bytes must be used
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, MultihashError> {
+        let (code, rest) = read_varint(bytes)?;
+        let (length, digest) = read_varint(rest)?;
+        if digest.len() as u64 != length {
+            return Err(MultihashError::InvalidLength);
+        }
+        Ok(Self {
+            code,
+            bytes: bytes.to_vec(),
+            digest_start: bytes.len() - digest.len(),
+        })
+    }
+    /// The code of the hash algorithm
+    pub fn code(&self) -> u64 {
+        self.code
+    }
+    /// The digest without the code and length
+    pub fn digest(&self) -> &[u8] {
+        &self.bytes[self.digest_start..]
+    }
+    /// The encoded multihash
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+    /// Returns the encoded multihash
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+    /// The CIDv1 of content with the codec. Such as [codec::RAW]
+    ///
+    /// Written in the default multibase. Lowercase base32 without padding and prefixed with `b`
+    pub fn to_cid_v1(&self, codec: u64) -> String {
+        let mut cid = Vec::with_capacity(self.bytes.len() + 10);
+        write_varint(&mut cid, 1);
+        write_varint(&mut cid, codec);
+        cid.extend_from_slice(&self.bytes);
+        let mut encoded = String::with_capacity(1 + (cid.len() * 8).div_ceil(5));
+        encoded.push('b');
+        encode_base32(&cid, &mut encoded);
+        encoded
+    }
+}
+impl AsRef<[u8]> for Multihash {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+impl Debug for Multihash {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Multihash")
+            .field("code", &format_args!("{:#x}", self.code))
+            .field("digest", &self.digest())
+            .finish()
+    }
+}
+/// Digests the encoded multihash
+impl Digestible for Multihash {
+    fn digest<B: ByteOrder, W: crate::DigestWriter>(&self, writer: &mut W) {
+        writer.write(&self.bytes);
+    }
+}
+
+/// An invalid encoded multihash
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MultihashError {
+    /// A varint ended early, is longer than 9 bytes or is not in its shortest form
+    InvalidVarint,
+    /// The length does not match the digest
+    InvalidLength,
+}
+impl Display for MultihashError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            MultihashError::InvalidVarint => f.write_str("invalid varint in multihash"),
+            MultihashError::InvalidLength => f.write_str("multihash length does not match digest"),
+        }
+    }
+}
+#[cfg(feature = "std")]
+impl std::error::Error for MultihashError {}
+
+/// Writes an unsigned LEB128 varint
+fn write_varint(bytes: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        bytes.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+/// Reads an unsigned varint. The multiformats spec allows up to 9 bytes
+///
+/// Only the shortest form is accepted. So every value has one encoding
+fn read_varint(bytes: &[u8]) -> Result<(u64, &[u8]), MultihashError> {
+    let mut value = 0u64;
+    for (index, byte) in bytes.iter().take(9).enumerate() {
+        value |= u64::from(byte & 0x7f) << (index * 7);
+        if byte & 0x80 == 0 {
+            // A trailing zero byte could have been left out
+            if *byte == 0 && index > 0 {
+                return Err(MultihashError::InvalidVarint);
+            }
+            return Ok((value, &bytes[index + 1..]));
+        }
+    }
+    Err(MultihashError::InvalidVarint)
+}
+/// RFC 4648 base32 in lowercase without padding
+fn encode_base32(bytes: &[u8], out: &mut String) {
+    const ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+    let mut buffer = 0u16;
+    let mut bits = 0;
+    for byte in bytes {
+        buffer = (buffer << 8) | u16::from(*byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(ALPHABET[usize::from((buffer >> bits) & 0x1f)] as char);
+        }
+    }
+    if bits > 0 {
+        out.push(ALPHABET[usize::from((buffer << (5 - bits)) & 0x1f)] as char);
+    }
+}
+
+/// A Type Wrapper for a Digester that returns a [Multihash]
+///
+/// Requires the `multihash` feature
+pub struct ToMultihash<D> {
+    digester: D,
+    code: u64,
+}
+impl<D> ToMultihash<D> {
+    /// Uses the code of the Digester
+    pub fn new(digester: D) -> Self
+    where
+        D: MultihashCode,
+    {
+        Self::with_code(digester, D::MULTIHASH_CODE)
+    }
+    /// Uses the given code. For Digesters without [MultihashCode]
+    pub fn with_code(digester: D, code: u64) -> Self {
+        Self { digester, code }
+    }
+    /// The code of the hash algorithm
+    pub fn code(&self) -> u64 {
+        self.code
+    }
+    /// Returns the inner Digester
+    pub fn into_inner(self) -> D {
+        self.digester
+    }
+}
+impl<D: Debug> Debug for ToMultihash<D> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ToMultihash")
+            .field("digester", &self.digester)
+            .field("code", &format_args!("{:#x}", self.code))
+            .finish()
+    }
+}
+impl<D: Clone> Clone for ToMultihash<D> {
+    fn clone(&self) -> Self {
+        Self::with_code(self.digester.clone(), self.code)
+    }
+}
+
+impl<D> AsRef<D> for ToMultihash<D> {
+    fn as_ref(&self) -> &D {
+        &self.digester
+    }
+}
+
+impl<D> AsMut<D> for ToMultihash<D> {
+    fn as_mut(&mut self) -> &mut D {
+        &mut self.digester
+    }
+}
+
+/// Add a method to all Digester types with a [MultihashCode] that turns the digester into a [ToMultihash] type
+pub trait IntoMultihash: Sized {
+    /// Wraps the Digester with its code
+    fn into_multihash(self) -> ToMultihash<Self>;
+}
+
+impl<D: Digester + MultihashCode> IntoMultihash for D
+where
+    <D as Digester>::Target: AsRef<[u8]>,
+{
+    fn into_multihash(self) -> ToMultihash<Self> {
+        ToMultihash::new(self)
+    }
+}
+impl<D: Digester> Digester for ToMultihash<D>
+where
+    <D as Digester>::Target: AsRef<[u8]>,
+{
+    type Target = Multihash;
+
+    fn digest<B: ByteOrder>(self, data: &impl Digestible) -> Self::Target {
+        Multihash::new(self.code, self.digester.digest::<B>(data).as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Multihash, MultihashError, ToMultihash};
+    use crate::Digester;
+    use byteorder::LittleEndian;
+    use sha2::{Digest, Sha256};
+
+    /// The multihash of the empty input
+    #[cfg(any(feature = "sha3", feature = "blake2", feature = "blake3"))]
+    fn empty<D: Digester + super::MultihashCode>(digester: D) -> Vec<u8>
+    where
+        D::Target: AsRef<[u8]>,
+    {
+        super::IntoMultihash::into_multihash(digester)
+            .digest::<LittleEndian>(&"")
+            .into_bytes()
+    }
+    #[cfg(any(feature = "sha3", feature = "blake2", feature = "blake3"))]
+    fn hex(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|index| u8::from_str_radix(&hex[index..index + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    #[cfg(feature = "sha3")]
+    fn sha3_vectors() {
+        let mut expected = vec![0x16, 0x20];
+        expected.extend(hex(
+            "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a",
+        ));
+        assert_eq!(empty(sha3::Sha3_256::new()), expected);
+        let mut expected = vec![0x1b, 0x20];
+        expected.extend(hex(
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470",
+        ));
+        assert_eq!(empty(sha3::Keccak256::new()), expected);
+    }
+
+    #[test]
+    #[cfg(feature = "blake2")]
+    fn blake2_vectors() {
+        let mut expected = vec![0xa0, 0xe4, 0x02, 0x20];
+        expected.extend(hex(
+            "0e5751c026e543b2e8ab2eb06099daa1d1e5df47778f7787faab45cdf12fe3a8",
+        ));
+        assert_eq!(
+            empty(blake2::Blake2b::<blake2::digest::consts::U32>::new()),
+            expected
+        );
+        let mut expected = vec![0xc0, 0xe4, 0x02, 0x40];
+        expected.extend(hex("786a02f742015903c6c6fd852552d272912f4740e15847618a86e217f71f5419d25e1031afee585313896444934eb04b903a685b1448b755d56f701afe9be2ce"));
+        assert_eq!(empty(blake2::Blake2b512::new()), expected);
+        let mut expected = vec![0xe0, 0xe4, 0x02, 0x20];
+        expected.extend(hex(
+            "69217a3079908094e11121d042354a7c1f55b6482ca1a51e1b250dfd1ed0eef9",
+        ));
+        assert_eq!(empty(blake2::Blake2s256::new()), expected);
+    }
+
+    #[test]
+    #[cfg(feature = "blake3")]
+    fn blake3_vectors() {
+        let mut expected = vec![0x1e, 0x20];
+        expected.extend(hex(
+            "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262",
+        ));
+        assert_eq!(empty(blake3::Hasher::new()), expected);
+    }
+
+    #[test]
+    #[cfg(feature = "sha2")]
+    fn sha2_vectors() {
+        use super::{codec, IntoMultihash};
+        use sha2::Sha512;
+        let multihash = Sha256::new()
+            .into_multihash()
+            .digest::<LittleEndian>(&"hello world");
+        assert_eq!(&multihash.as_bytes()[..2], &[0x12, 0x20]);
+        assert_eq!(
+            multihash.digest(),
+            <Sha256 as Digest>::digest(b"hello world").as_slice()
+        );
+        assert_eq!(
+            multihash.to_cid_v1(codec::RAW),
+            "bafkreifzjut3te2nhyekklss27nh3k72ysco7y32koao5eei66wof36n5e"
+        );
+
+        let multihash = Sha512::new()
+            .into_multihash()
+            .digest::<LittleEndian>(&"hello world");
+        // The length 64 fits in one varint byte
+        assert_eq!(&multihash.as_bytes()[..2], &[0x13, 0x40]);
+        assert_eq!(multihash.as_bytes().len(), 66);
+    }
+
+    #[test]
+    fn custom_code_and_round_trip() {
+        let multihash = ToMultihash::with_code(Sha256::new(), 0xb220).digest::<LittleEndian>(&1u8);
+        // Codes over 127 take more than one varint byte
+        assert_eq!(&multihash.as_bytes()[..4], &[0xa0, 0xe4, 0x02, 0x20]);
+        assert_eq!(Multihash::from_bytes(multihash.as_bytes()), Ok(multihash));
+
+        assert_eq!(
+            Multihash::from_bytes(&[0x12, 0x20, 0]),
+            Err(MultihashError::InvalidLength)
+        );
+        assert_eq!(
+            Multihash::from_bytes(&[0x80]),
+            Err(MultihashError::InvalidVarint)
+        );
+        // 0x12 written in two bytes
+        assert_eq!(
+            Multihash::from_bytes(&[0x92, 0x00, 0x00]),
+            Err(MultihashError::InvalidVarint)
+        );
+        assert_eq!(
+            Multihash::from_bytes(&[0x00, 0x00]),
+            Ok(Multihash::new(0, &[]))
+        );
+    }
+}
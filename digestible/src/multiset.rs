@@ -0,0 +1,377 @@
+/*!
+# Multiset Digests
+
+Digests a collection without depending on the order of its items.
+
+Every item is digested on its own with SHA-256.
+The hashes are added together as 256 bit little endian integers, wrapping on overflow.
+Addition does not depend on the order. So no sorting or allocation is needed.
+And items can be added or removed in O(1) with [MultisetDigest].
+
+A [MultisetDigest] is written as the number of items as a u64 followed by the 32 bytes of the sum.
+Items are digested without the key of [keyed](crate::keyed) fields.
+[insert](MultisetDigest::insert) and [remove](MultisetDigest::remove) use [FormatV1].
+Use [insert_v](MultisetDigest::insert_v) and [remove_v](MultisetDigest::remove_v) to pick another [format](crate::format).
+With [Legacy](crate::format::Legacy) the items `("a", "bc")` and `("ab", "c")` have the same hash.
+[unordered](crate::digest_with::unordered) uses the format and key of its writer instead.
+
+Collisions can be forged for sums of many chosen items more easily than for SHA-256 itself.
+Use the sorted encoding of `HashSet` and `HashMap` if the items are chosen by an attacker.
+
+## Example
+```rust
+use digestible::multiset::MultisetDigest;
+use byteorder::LittleEndian;
+let mut a = MultisetDigest::new();
+a.insert::<LittleEndian>(&"apple");
+a.insert::<LittleEndian>(&"pear");
+
+let mut b = MultisetDigest::new();
+b.insert::<LittleEndian>(&"pear");
+b.insert::<LittleEndian>(&"plum");
+b.insert::<LittleEndian>(&"apple");
+b.remove::<LittleEndian>(&"plum");
+assert_eq!(a, b);
+```
+*/
+use crate::format::{EncodingFormat, Format, FormatV1};
+use crate::sha256::Sha256Hasher;
+use crate::{DigestWriter, Digestible};
+use byteorder::ByteOrder;
+use core::marker::PhantomData;
+
+/// An order independent digest of a multiset. See [module docs](self)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct MultisetDigest {
+    /// Little endian 64 bit limbs of the sum
+    sum: [u64; 4],
+    count: u64,
+}
+impl MultisetDigest {
+    /// An empty multiset
+    pub const fn new() -> Self {
+        Self {
+            sum: [0; 4],
+            count: 0,
+        }
+    }
+    /// Adds the item digested with [FormatV1]
+    pub fn insert<B: ByteOrder>(&mut self, item: &impl Digestible) {
+        self.insert_v::<FormatV1, B>(item);
+    }
+    /// Removes the item digested with [FormatV1].
+    ///
+    /// Removing an item that was never inserted gives a digest that no set of inserts can reach
+    pub fn remove<B: ByteOrder>(&mut self, item: &impl Digestible) {
+        self.remove_v::<FormatV1, B>(item);
+    }
+    /// Adds the item digested with the format `F`
+    pub fn insert_v<F: EncodingFormat, B: ByteOrder>(&mut self, item: &impl Digestible) {
+        self.insert_as::<B, FormatOnly<F>>(item, None);
+    }
+    /// Removes the item digested with the format `F`. See [remove](Self::remove)
+    pub fn remove_v<F: EncodingFormat, B: ByteOrder>(&mut self, item: &impl Digestible) {
+        self.remove_as::<B, FormatOnly<F>>(item, None);
+    }
+    /// Adds every item of the other multiset
+    pub fn merge(&mut self, other: &Self) {
+        self.add(&other.sum);
+        self.count = self.count.wrapping_add(other.count);
+    }
+    /// The number of items
+    pub fn len(&self) -> u64 {
+        self.count
+    }
+    /// If there are no items
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+    /// The sum of the item hashes as little endian bytes
+    pub fn sum(&self) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        for (chunk, limb) in bytes.chunks_exact_mut(8).zip(self.sum) {
+            chunk.copy_from_slice(&limb.to_le_bytes());
+        }
+        bytes
+    }
+    /// Adds the item digested using the format of `W` and the [key](DigestWriter::digest_key)
+    pub(crate) fn insert_as<B: ByteOrder, W: DigestWriter + ?Sized>(
+        &mut self,
+        item: &impl Digestible,
+        key: Option<&[u8]>,
+    ) {
+        self.add(&hash_item::<B, W>(item, key));
+        self.count = self.count.wrapping_add(1);
+    }
+    fn remove_as<B: ByteOrder, W: DigestWriter + ?Sized>(
+        &mut self,
+        item: &impl Digestible,
+        key: Option<&[u8]>,
+    ) {
+        self.sub(&hash_item::<B, W>(item, key));
+        self.count = self.count.wrapping_sub(1);
+    }
+    fn add(&mut self, other: &[u64; 4]) {
+        let mut carry = false;
+        for (limb, other) in self.sum.iter_mut().zip(other) {
+            let (value, overflow_a) = limb.overflowing_add(*other);
+            let (value, overflow_b) = value.overflowing_add(carry as u64);
+            *limb = value;
+            carry = overflow_a || overflow_b;
+        }
+    }
+    fn sub(&mut self, other: &[u64; 4]) {
+        let mut borrow = false;
+        for (limb, other) in self.sum.iter_mut().zip(other) {
+            let (value, overflow_a) = limb.overflowing_sub(*other);
+            let (value, overflow_b) = value.overflowing_sub(borrow as u64);
+            *limb = value;
+            borrow = overflow_a || overflow_b;
+        }
+    }
+}
+/// Builds a multiset from the items. Uses NativeEndian and [FormatV1]
+impl<D: Digestible> FromIterator<D> for MultisetDigest {
+    fn from_iter<T: IntoIterator<Item = D>>(iter: T) -> Self {
+        let mut multiset = Self::new();
+        for item in iter {
+            multiset.insert::<byteorder::NativeEndian>(&item);
+        }
+        multiset
+    }
+}
+/// Writes the number of items as a u64 then the sum
+impl Digestible for MultisetDigest {
+    fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
+        writer.write_u64::<B>(self.count);
+        writer.write(&self.sum());
+    }
+}
+
+/// Hashes one item with SHA-256. Uses the format of `W` and the key of `salted` and `keyed` fields
+fn hash_item<B: ByteOrder, W: DigestWriter + ?Sized>(
+    item: &impl Digestible,
+    key: Option<&[u8]>,
+) -> [u64; 4] {
+    let mut hasher = ItemHasher::<W> {
+        hasher: Sha256Hasher::new(),
+        key,
+        _writer: PhantomData,
+    };
+    item.digest::<B, _>(&mut hasher);
+    let hash = hasher.hasher.finalize();
+    let mut limbs = [0u64; 4];
+    for (limb, chunk) in limbs.iter_mut().zip(hash.chunks_exact(8)) {
+        *limb = u64::from_le_bytes(chunk.try_into().expect("chunks are 8 bytes"));
+    }
+    limbs
+}
+/// Only used for its format
+struct FormatOnly<F>(PhantomData<F>);
+impl<F: EncodingFormat> DigestWriter for FormatOnly<F> {
+    const FORMAT: Format = F::FORMAT;
+    fn write(&mut self, _: &[u8]) {}
+}
+struct ItemHasher<'k, W: ?Sized> {
+    hasher: Sha256Hasher,
+    key: Option<&'k [u8]>,
+    _writer: PhantomData<fn() -> *const W>,
+}
+impl<W: DigestWriter + ?Sized> DigestWriter for ItemHasher<'_, W> {
+    const FORMAT: Format = W::FORMAT;
+    fn write(&mut self, data: &[u8]) {
+        self.hasher.update(data);
+    }
+    fn digest_key(&self) -> Option<&[u8]> {
+        self.key
+    }
+}
+
+/// A collection that can be digested with [unordered](crate::digest_with::unordered)
+pub trait UnorderedCollection {
+    /// The items of the collection
+    type Item<'a>: Digestible
+    where
+        Self: 'a;
+    /// Iterates over the items in any order
+    type Iter<'a>: Iterator<Item = Self::Item<'a>>
+    where
+        Self: 'a;
+    /// The items of the collection
+    fn unordered_items(&self) -> Self::Iter<'_>;
+}
+impl<T: Digestible> UnorderedCollection for [T] {
+    type Item<'a>
+        = &'a T
+    where
+        T: 'a;
+    type Iter<'a>
+        = core::slice::Iter<'a, T>
+    where
+        T: 'a;
+    fn unordered_items(&self) -> Self::Iter<'_> {
+        self.iter()
+    }
+}
+#[cfg(feature = "alloc")]
+mod alloc_types {
+    use super::UnorderedCollection;
+    use crate::Digestible;
+    use alloc::collections::{binary_heap, BinaryHeap};
+    use alloc::vec::Vec;
+
+    impl<T: Digestible> UnorderedCollection for Vec<T> {
+        type Item<'a>
+            = &'a T
+        where
+            T: 'a;
+        type Iter<'a>
+            = core::slice::Iter<'a, T>
+        where
+            T: 'a;
+        fn unordered_items(&self) -> Self::Iter<'_> {
+            self.iter()
+        }
+    }
+    impl<T: Digestible> UnorderedCollection for BinaryHeap<T> {
+        type Item<'a>
+            = &'a T
+        where
+            T: 'a;
+        type Iter<'a>
+            = binary_heap::Iter<'a, T>
+        where
+            T: 'a;
+        fn unordered_items(&self) -> Self::Iter<'_> {
+            self.iter()
+        }
+    }
+}
+#[cfg(feature = "std")]
+mod std_types {
+    use super::UnorderedCollection;
+    use crate::Digestible;
+    use std::collections::{hash_map, hash_set, HashMap, HashSet};
+
+    impl<T: Digestible, S> UnorderedCollection for HashSet<T, S> {
+        type Item<'a>
+            = &'a T
+        where
+            Self: 'a;
+        type Iter<'a>
+            = hash_set::Iter<'a, T>
+        where
+            Self: 'a;
+        fn unordered_items(&self) -> Self::Iter<'_> {
+            self.iter()
+        }
+    }
+    /// Each key and value pair is one item
+    impl<K: Digestible, V: Digestible, S> UnorderedCollection for HashMap<K, V, S> {
+        type Item<'a>
+            = (&'a K, &'a V)
+        where
+            Self: 'a;
+        type Iter<'a>
+            = hash_map::Iter<'a, K, V>
+        where
+            Self: 'a;
+        fn unordered_items(&self) -> Self::Iter<'_> {
+            self.iter()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MultisetDigest;
+    use crate::format::{FormatV1, FormatWriter, Legacy};
+    use crate::Digestible;
+    use byteorder::LittleEndian;
+    use std::collections::{HashMap, HashSet};
+
+    fn digest(value: &impl Digestible) -> Vec<u8> {
+        let mut digest = Vec::new();
+        value.digest::<LittleEndian, _>(&mut digest);
+        digest
+    }
+
+    #[test]
+    fn order_independent() {
+        let forward: MultisetDigest = (0..100u32).collect();
+        let backward: MultisetDigest = (0..100u32).rev().collect();
+        assert_eq!(forward, backward);
+        assert_eq!(forward.len(), 100);
+    }
+
+    #[test]
+    fn insert_remove() {
+        let mut multiset = MultisetDigest::new();
+        multiset.insert::<LittleEndian>(&1u32);
+        multiset.insert::<LittleEndian>(&1u32);
+        let twice = multiset;
+        multiset.remove::<LittleEndian>(&1u32);
+        assert_ne!(multiset, twice);
+        assert_eq!(multiset, [1u32].into_iter().collect());
+        multiset.remove::<LittleEndian>(&1u32);
+        assert_eq!(multiset, MultisetDigest::new());
+        assert!(multiset.is_empty());
+    }
+
+    #[test]
+    fn merge() {
+        let mut a: MultisetDigest = ["a", "b"].into_iter().collect();
+        let b: MultisetDigest = ["c"].into_iter().collect();
+        a.merge(&b);
+        assert_eq!(a, ["c", "b", "a"].into_iter().collect());
+    }
+
+    #[test]
+    fn unordered() {
+        let set: HashSet<u32> = (0..64).collect();
+        let vec: Vec<u32> = (0..64).rev().collect();
+        let mut set_digest = Vec::new();
+        crate::digest_with::unordered::<LittleEndian, _>(&set, &mut set_digest);
+        let mut vec_digest = Vec::new();
+        crate::digest_with::unordered::<LittleEndian, _>(&vec, &mut vec_digest);
+        assert_eq!(set_digest, vec_digest);
+
+        let mut multiset = MultisetDigest::new();
+        for item in &vec {
+            multiset.insert::<LittleEndian>(item);
+        }
+        assert_eq!(set_digest, digest(&multiset));
+        assert_eq!(&set_digest[..8], &64u64.to_le_bytes());
+    }
+
+    #[test]
+    fn insert_uses_format() {
+        let mut a = MultisetDigest::new();
+        a.insert::<LittleEndian>(&("a", "bc"));
+        let mut b = MultisetDigest::new();
+        b.insert::<LittleEndian>(&("ab", "c"));
+        assert_ne!(a, b);
+
+        let mut v1 = MultisetDigest::new();
+        v1.insert_v::<FormatV1, LittleEndian>(&("a", "bc"));
+        assert_eq!(a, v1);
+        let mut a = MultisetDigest::new();
+        a.insert_v::<Legacy, LittleEndian>(&("a", "bc"));
+        let mut b = MultisetDigest::new();
+        b.insert_v::<Legacy, LittleEndian>(&("ab", "c"));
+        assert_eq!(a, b);
+        a.remove_v::<Legacy, LittleEndian>(&("ab", "c"));
+        assert!(a.is_empty());
+        assert_eq!(a, MultisetDigest::new());
+    }
+
+    #[test]
+    fn unordered_uses_format() {
+        let map = HashMap::from([("a", 1u8)]);
+        let mut legacy = Vec::new();
+        crate::digest_with::unordered::<LittleEndian, _>(&map, &mut legacy);
+        let mut v1 = FormatWriter::<FormatV1, _>::new(Vec::new());
+        crate::digest_with::unordered::<LittleEndian, _>(&map, &mut v1);
+        assert_ne!(legacy, v1.into_inner());
+    }
+}
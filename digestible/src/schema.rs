@@ -0,0 +1,406 @@
+/*!
+# Schema Fingerprints
+
+A [DigestSchema] fingerprint describes how a type is digested. Not the value.
+If the fingerprint of a type changes, stored digests of that type should be considered stale.
+
+The derive macro implements [DigestSchema] with `#[digestible(schema)]`.
+The fingerprint is computed at compile time from
+- the name of the type and if it is a struct, enum or union
+- the container attributes
+- every field name and its attributes
+- every enum variant and its fields
+- the fingerprints of the field types
+
+Wrappers that are digested the same as the type inside them have the same fingerprint as that type.
+So changing a field from `u32` to `Box<u32>` does not change the fingerprint.
+
+Recursive types can not implement [DigestSchema]. Computing the fingerprint would never end.
+
+## Example
+```rust
+use digestible::schema::DigestSchema;
+use digestible::Digestible;
+#[derive(Digestible)]
+#[digestible(schema)]
+pub struct Version1 {
+    pub id: u32,
+    pub name: String,
+}
+#[derive(Digestible)]
+#[digestible(schema)]
+pub struct Version2 {
+    pub id: u64,
+    pub name: String,
+}
+assert_ne!(Version1::SCHEMA_FINGERPRINT, Version2::SCHEMA_FINGERPRINT);
+```
+
+## Implementing DigestSchema by hand
+```rust
+use digestible::schema::{DigestSchema, SchemaHasher};
+pub struct Celsius(f64);
+impl DigestSchema for Celsius {
+    const SCHEMA_FINGERPRINT: [u8; 32] = SchemaHasher::new("Celsius")
+        .fingerprint(f64::SCHEMA_FINGERPRINT)
+        .finish();
+}
+```
+*/
+use crate::sha256::Sha256;
+
+/// A fingerprint of how the type is digested. See [module docs](self)
+pub trait DigestSchema {
+    /// The SHA-256 fingerprint of the schema
+    const SCHEMA_FINGERPRINT: [u8; 32];
+}
+
+/// Builds a fingerprint at compile time.
+///
+/// Every value is framed so different sequences of calls give different fingerprints
+#[derive(Debug, Clone, Copy)]
+pub struct SchemaHasher(Sha256);
+impl SchemaHasher {
+    /// Starts a fingerprint for the kind of type. Such as the name of the type
+    pub const fn new(kind: &str) -> Self {
+        Self(Sha256::new()).str(kind)
+    }
+    /// Adds a string
+    pub const fn str(self, value: &str) -> Self {
+        self.len(value.len()).bytes(value.as_bytes())
+    }
+    /// Adds a length or count
+    pub const fn len(self, len: usize) -> Self {
+        self.bytes(&(len as u64).to_le_bytes())
+    }
+    /// Adds a bool
+    pub const fn bool(self, value: bool) -> Self {
+        self.bytes(&[value as u8])
+    }
+    /// Adds the fingerprint of another type
+    pub const fn fingerprint(self, fingerprint: [u8; 32]) -> Self {
+        self.bytes(&fingerprint)
+    }
+    const fn bytes(self, bytes: &[u8]) -> Self {
+        Self(self.0.update(bytes))
+    }
+    /// Returns the fingerprint
+    pub const fn finish(self) -> [u8; 32] {
+        self.0.finalize()
+    }
+}
+
+macro_rules! schema_for_leaf {
+    ($($name:literal => $($ty:ty),+;)*) => {
+        $(
+            $(
+                impl DigestSchema for $ty {
+                    const SCHEMA_FINGERPRINT: [u8; 32] = SchemaHasher::new($name).finish();
+                }
+            )+
+        )*
+    };
+}
+macro_rules! schema_for_generic {
+    ($($name:literal => $ty:ident<$($param:ident),+>;)*) => {
+        $(
+            impl<$($param: DigestSchema),+> DigestSchema for $ty<$($param),+> {
+                const SCHEMA_FINGERPRINT: [u8; 32] = SchemaHasher::new($name)
+                    $(.fingerprint($param::SCHEMA_FINGERPRINT))+
+                    .finish();
+            }
+        )*
+    };
+}
+/// The type is digested the same as `T`
+macro_rules! schema_for_wrapper {
+    ($($ty:ident),*) => {
+        $(
+            impl<T: DigestSchema + ?Sized> DigestSchema for $ty<T> {
+                const SCHEMA_FINGERPRINT: [u8; 32] = T::SCHEMA_FINGERPRINT;
+            }
+        )*
+    };
+}
+macro_rules! schema_for_same_as {
+    ($($ty:ty => $same_as:ty),*) => {
+        $(
+            impl DigestSchema for $ty {
+                const SCHEMA_FINGERPRINT: [u8; 32] = <$same_as>::SCHEMA_FINGERPRINT;
+            }
+        )*
+    };
+}
+
+mod core_types {
+    use super::{DigestSchema, SchemaHasher};
+    use core::cell::{Cell, RefCell};
+    use core::cmp::{Ordering, Reverse};
+    use core::ffi::CStr;
+    use core::marker::PhantomData;
+    use core::num::{
+        NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+        NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, Saturating, Wrapping,
+    };
+    use core::ops::{Bound, Range, RangeFrom, RangeInclusive, RangeTo, RangeToInclusive};
+    use core::time::Duration;
+
+    schema_for_leaf!(
+        "u8" => u8;
+        "u16" => u16;
+        "u32" => u32;
+        "u64" => u64;
+        "u128" => u128;
+        "usize" => usize;
+        "i8" => i8;
+        "i16" => i16;
+        "i32" => i32;
+        "i64" => i64;
+        "i128" => i128;
+        "isize" => isize;
+        "f32" => f32;
+        "f64" => f64;
+        "bool" => bool;
+        "char" => char;
+        "str" => str;
+        "c_str" => CStr;
+        "ordering" => Ordering;
+        "range_full" => core::ops::RangeFull;
+        "duration" => Duration;
+        "()" => ();
+    );
+    schema_for_same_as!(
+        NonZeroU8 => u8,
+        NonZeroU16 => u16,
+        NonZeroU32 => u32,
+        NonZeroU64 => u64,
+        NonZeroU128 => u128,
+        NonZeroUsize => usize,
+        NonZeroI8 => i8,
+        NonZeroI16 => i16,
+        NonZeroI32 => i32,
+        NonZeroI64 => i64,
+        NonZeroI128 => i128,
+        NonZeroIsize => isize
+    );
+    schema_for_generic!(
+        "option" => Option<T>;
+        "result" => Result<T, E>;
+        "range" => Range<T>;
+        "range_inclusive" => RangeInclusive<T>;
+        "range_from" => RangeFrom<T>;
+        "range_to" => RangeTo<T>;
+        "range_to_inclusive" => RangeToInclusive<T>;
+        "bound" => Bound<T>;
+    );
+    schema_for_wrapper!(RefCell);
+    impl<T: DigestSchema> DigestSchema for Wrapping<T> {
+        const SCHEMA_FINGERPRINT: [u8; 32] = T::SCHEMA_FINGERPRINT;
+    }
+    impl<T: DigestSchema> DigestSchema for Saturating<T> {
+        const SCHEMA_FINGERPRINT: [u8; 32] = T::SCHEMA_FINGERPRINT;
+    }
+    impl<T: DigestSchema> DigestSchema for Reverse<T> {
+        const SCHEMA_FINGERPRINT: [u8; 32] = T::SCHEMA_FINGERPRINT;
+    }
+    impl<T: DigestSchema> DigestSchema for Cell<T> {
+        const SCHEMA_FINGERPRINT: [u8; 32] = T::SCHEMA_FINGERPRINT;
+    }
+    impl<T: DigestSchema + ?Sized> DigestSchema for &T {
+        const SCHEMA_FINGERPRINT: [u8; 32] = T::SCHEMA_FINGERPRINT;
+    }
+    /// Same as a Vec
+    impl<T: DigestSchema> DigestSchema for [T] {
+        const SCHEMA_FINGERPRINT: [u8; 32] = SchemaHasher::new("vec")
+            .fingerprint(T::SCHEMA_FINGERPRINT)
+            .finish();
+    }
+    impl<T: ?Sized> DigestSchema for PhantomData<T> {
+        const SCHEMA_FINGERPRINT: [u8; 32] = SchemaHasher::new("phantom").finish();
+    }
+
+    macro_rules! schema_for_tuple {
+        ($($T:ident,)*) => {
+            impl<$($T: DigestSchema,)*> DigestSchema for ($($T,)*) {
+                const SCHEMA_FINGERPRINT: [u8; 32] = SchemaHasher::new("tuple")
+                    $(.fingerprint($T::SCHEMA_FINGERPRINT))*
+                    .finish();
+            }
+        };
+    }
+    schema_for_tuple!(T1,);
+    schema_for_tuple!(T1, T2,);
+    schema_for_tuple!(T1, T2, T3,);
+    schema_for_tuple!(T1, T2, T3, T4,);
+    schema_for_tuple!(T1, T2, T3, T4, T5,);
+    schema_for_tuple!(T1, T2, T3, T4, T5, T6,);
+    schema_for_tuple!(T1, T2, T3, T4, T5, T6, T7,);
+    schema_for_tuple!(T1, T2, T3, T4, T5, T6, T7, T8,);
+    schema_for_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9,);
+    schema_for_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10,);
+    schema_for_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11,);
+    schema_for_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12,);
+    schema_for_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13,);
+    schema_for_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14,);
+    schema_for_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15,);
+    schema_for_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15, T16,);
+}
+#[cfg(feature = "alloc")]
+mod alloc_types {
+    use super::{DigestSchema, SchemaHasher};
+    use alloc::borrow::{Cow, ToOwned};
+    use alloc::boxed::Box;
+    use alloc::collections::{BTreeMap, BTreeSet, BinaryHeap, LinkedList, VecDeque};
+    use alloc::ffi::CString;
+    use alloc::rc::{Rc, Weak as WeakRc};
+    use alloc::string::String;
+    use alloc::sync::{Arc, Weak as WeakArc};
+    use alloc::vec::Vec;
+
+    schema_for_same_as!(String => str, CString => core::ffi::CStr);
+    schema_for_wrapper!(Box, Rc, Arc);
+    schema_for_generic!(
+        "vec" => Vec<T>;
+        "vec_deque" => VecDeque<T>;
+        "linked_list" => LinkedList<T>;
+        "b_tree_set" => BTreeSet<T>;
+        "binary_heap" => BinaryHeap<T>;
+        "b_tree_map" => BTreeMap<K, V>;
+        "option" => WeakRc<T>;
+        "option" => WeakArc<T>;
+    );
+    impl<T: DigestSchema + ToOwned + ?Sized> DigestSchema for Cow<'_, T> {
+        const SCHEMA_FINGERPRINT: [u8; 32] = T::SCHEMA_FINGERPRINT;
+    }
+}
+#[cfg(feature = "std")]
+mod std_types {
+    use super::{DigestSchema, SchemaHasher};
+    use std::collections::{HashMap, HashSet};
+    use std::ffi::{OsStr, OsString};
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+    use std::path::{Path, PathBuf};
+    use std::sync::{Mutex, RwLock};
+    use std::time::SystemTime;
+
+    schema_for_leaf!(
+        "os_str" => OsStr;
+        "path" => Path;
+        "ipv4_addr" => Ipv4Addr;
+        "ipv6_addr" => Ipv6Addr;
+        "ip_addr" => IpAddr;
+        "socket_addr_v4" => SocketAddrV4;
+        "socket_addr_v6" => SocketAddrV6;
+        "socket_addr" => SocketAddr;
+        "system_time" => SystemTime;
+    );
+    schema_for_same_as!(OsString => OsStr, PathBuf => Path);
+    schema_for_wrapper!(Mutex, RwLock);
+    /// The hasher does not change how the entries are digested
+    impl<K: DigestSchema, V: DigestSchema, S> DigestSchema for HashMap<K, V, S> {
+        const SCHEMA_FINGERPRINT: [u8; 32] = SchemaHasher::new("hash_map")
+            .fingerprint(K::SCHEMA_FINGERPRINT)
+            .fingerprint(V::SCHEMA_FINGERPRINT)
+            .finish();
+    }
+    /// The hasher does not change how the values are digested
+    impl<T: DigestSchema, S> DigestSchema for HashSet<T, S> {
+        const SCHEMA_FINGERPRINT: [u8; 32] = SchemaHasher::new("hash_set")
+            .fingerprint(T::SCHEMA_FINGERPRINT)
+            .finish();
+    }
+}
+/// chrono and time types with the same encoding have the same fingerprint
+#[cfg(feature = "chrono")]
+mod chrono_types {
+    use super::{DigestSchema, SchemaHasher};
+    use chrono::{
+        DateTime, Days, Duration, Month, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Weekday,
+    };
+    schema_for_leaf!(
+        "primitive_date_time" => NaiveDateTime;
+        "signed_duration" => Duration;
+        "date" => NaiveDate;
+        "weekday" => Weekday;
+        "month" => Month;
+        "days" => Days;
+        "time" => NaiveTime;
+    );
+    impl<Tz: TimeZone> DigestSchema for DateTime<Tz> {
+        const SCHEMA_FINGERPRINT: [u8; 32] = SchemaHasher::new("offset_date_time").finish();
+    }
+}
+#[cfg(feature = "time")]
+mod time_types {
+    use super::{DigestSchema, SchemaHasher};
+    use time::{
+        Date, Duration, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset, Weekday,
+    };
+    schema_for_leaf!(
+        "offset_date_time" => OffsetDateTime;
+        "primitive_date_time" => PrimitiveDateTime;
+        "date" => Date;
+        "time" => Time;
+        "utc_offset" => UtcOffset;
+        "signed_duration" => Duration;
+        "month" => Month;
+        "weekday" => Weekday;
+    );
+}
+#[cfg(feature = "uuid")]
+mod uuid_types {
+    use super::{DigestSchema, SchemaHasher};
+    schema_for_leaf!("uuid" => uuid::Uuid;);
+}
+#[cfg(feature = "bytes")]
+mod bytes_types {
+    use super::{DigestSchema, SchemaHasher};
+    schema_for_leaf!("bytes" => bytes::Bytes, bytes::BytesMut;);
+}
+#[cfg(feature = "serde_json")]
+mod serde_json_types {
+    use super::{DigestSchema, SchemaHasher};
+    schema_for_leaf!(
+        "json_value" => serde_json::Value;
+        "json_number" => serde_json::Number;
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DigestSchema;
+
+    #[test]
+    fn wrappers_match() {
+        assert_eq!(Box::<u32>::SCHEMA_FINGERPRINT, u32::SCHEMA_FINGERPRINT);
+        assert_eq!(String::SCHEMA_FINGERPRINT, <&str>::SCHEMA_FINGERPRINT);
+        assert_eq!(
+            core::num::NonZeroU8::SCHEMA_FINGERPRINT,
+            u8::SCHEMA_FINGERPRINT
+        );
+    }
+
+    #[test]
+    fn generics_differ() {
+        assert_ne!(
+            Vec::<u32>::SCHEMA_FINGERPRINT,
+            Vec::<u64>::SCHEMA_FINGERPRINT
+        );
+        assert_ne!(
+            Option::<u32>::SCHEMA_FINGERPRINT,
+            Vec::<u32>::SCHEMA_FINGERPRINT
+        );
+        assert_ne!(
+            <(u8, u16)>::SCHEMA_FINGERPRINT,
+            <(u16, u8)>::SCHEMA_FINGERPRINT
+        );
+        assert_ne!(
+            core::ops::Range::<u32>::SCHEMA_FINGERPRINT,
+            core::ops::RangeInclusive::<u32>::SCHEMA_FINGERPRINT
+        );
+        assert_ne!(
+            core::ops::RangeTo::<u32>::SCHEMA_FINGERPRINT,
+            core::ops::RangeToInclusive::<u32>::SCHEMA_FINGERPRINT
+        );
+    }
+}
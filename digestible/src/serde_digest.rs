@@ -0,0 +1,536 @@
+/*!
+# Digesting serde Types
+
+[DigestSerializer] is a [Serializer] that writes into a [DigestWriter].
+So any type that implements [Serialize] can be digested.
+
+Use [SerdeDigest] to digest a value or
+[digest_serialize](crate::digest_with::digest_serialize) on a field.
+
+## Encoding
+Every value starts with a u8 tag. Numbers are written with the ByteOrder `B`.
+
+- Strings and bytes are written with their length in bytes as a u64.
+- Tuples write their length as a u64 followed by each element.
+- Sequences and maps write each element followed by the end tag and the number of elements as a u64.
+  The length is written at the end so serializers that do not know the length up front are supported.
+- Structs write the number of fields as a u64 followed by each field.
+- Enum variants write the variant index as a u32.
+- Names of structs, fields and variants are only written when enabled with [DigestSerializer::with_names].
+  They are written as strings before the index or value.
+
+## Map Order
+Sequences and maps are written in the order the Serialize implementation gives them.
+Nothing is sorted. So a `HashMap` or `HashSet` gives a different digest depending on its iteration order.
+Use a `BTreeMap` or `BTreeSet`, or serde's `serialize_with` to sort them, if the digest has to be stable.
+The [Digestible] implementations of `HashMap` and `HashSet` do sort their entries.
+
+## Example
+```rust
+use digestible::serde_digest::SerdeDigest;
+use digestible::Digester;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+#[derive(Serialize)]
+pub struct ThirdParty {
+    pub id: u32,
+    pub tags: Vec<String>,
+}
+#[derive(digestible::Digestible)]
+pub struct MyStruct {
+    #[digestible(digest_with = digest_serialize)]
+    pub third_party: ThirdParty,
+}
+let value = ThirdParty {
+    id: 1,
+    tags: vec!["a".to_string()],
+};
+let result = Sha256::new().digest_native(&SerdeDigest(&value));
+assert_eq!(result.len(), 32);
+```
+*/
+use crate::{DigestWriter, Digestible};
+use alloc::string::{String, ToString};
+use byteorder::{ByteOrder, NativeEndian};
+use core::fmt::{Display, Formatter};
+use core::marker::PhantomData;
+use serde::ser::{
+    SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant, SerializeTuple,
+    SerializeTupleStruct, SerializeTupleVariant,
+};
+use serde::{Serialize, Serializer};
+
+/// The tags written before every value
+#[allow(missing_docs)]
+pub mod tags {
+    pub const BOOL: u8 = 0;
+    pub const I8: u8 = 1;
+    pub const I16: u8 = 2;
+    pub const I32: u8 = 3;
+    pub const I64: u8 = 4;
+    pub const I128: u8 = 5;
+    pub const U8: u8 = 6;
+    pub const U16: u8 = 7;
+    pub const U32: u8 = 8;
+    pub const U64: u8 = 9;
+    pub const U128: u8 = 10;
+    pub const F32: u8 = 11;
+    pub const F64: u8 = 12;
+    /// Written as a u32
+    pub const CHAR: u8 = 13;
+    pub const STR: u8 = 14;
+    pub const BYTES: u8 = 15;
+    pub const NONE: u8 = 16;
+    pub const SOME: u8 = 17;
+    pub const UNIT: u8 = 18;
+    pub const UNIT_STRUCT: u8 = 19;
+    pub const UNIT_VARIANT: u8 = 20;
+    pub const NEWTYPE_STRUCT: u8 = 21;
+    pub const NEWTYPE_VARIANT: u8 = 22;
+    pub const SEQ: u8 = 23;
+    pub const TUPLE: u8 = 24;
+    pub const TUPLE_STRUCT: u8 = 25;
+    pub const TUPLE_VARIANT: u8 = 26;
+    pub const MAP: u8 = 27;
+    pub const STRUCT: u8 = 28;
+    pub const STRUCT_VARIANT: u8 = 29;
+    /// Ends a sequence or map
+    pub const END: u8 = 30;
+}
+
+/// An error returned by the [Serialize] implementation of the value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error(pub String);
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+impl serde::ser::StdError for Error {}
+impl serde::ser::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self(msg.to_string())
+    }
+}
+
+/// A [Serializer] that writes into a [DigestWriter]. See [module docs](self)
+pub struct DigestSerializer<'w, W: DigestWriter, B: ByteOrder = NativeEndian> {
+    writer: &'w mut W,
+    names: bool,
+    _byte_order: PhantomData<B>,
+}
+impl<'w, W: DigestWriter, B: ByteOrder> DigestSerializer<'w, W, B> {
+    /// Creates a serializer that does not write names
+    pub fn new(writer: &'w mut W) -> Self {
+        Self {
+            writer,
+            names: false,
+            _byte_order: PhantomData,
+        }
+    }
+    /// Also write the names of structs, fields and variants
+    pub fn with_names(mut self, names: bool) -> Self {
+        self.names = names;
+        self
+    }
+    #[inline]
+    fn write_str(&mut self, value: &str) {
+        self.write_bytes(value.as_bytes());
+    }
+    #[inline]
+    fn write_bytes(&mut self, value: &[u8]) {
+        self.writer.write_u64::<B>(value.len() as u64);
+        self.writer.write(value);
+    }
+    #[inline]
+    fn write_name(&mut self, name: &str) {
+        if self.names {
+            self.write_str(name);
+        }
+    }
+    #[inline]
+    fn write_variant(&mut self, tag: u8, name: &str, variant_index: u32, variant: &str) {
+        self.writer.write_u8(tag);
+        self.write_name(name);
+        self.write_name(variant);
+        self.writer.write_u32::<B>(variant_index);
+    }
+}
+
+macro_rules! serialize_num {
+    ($($fn_name:ident($ty:ty) => $tag:ident, $write:ident),*) => {
+        $(
+            fn $fn_name(self, v: $ty) -> Result<Self::Ok, Self::Error> {
+                self.writer.write_u8(tags::$tag);
+                self.writer.$write::<B>(v);
+                Ok(())
+            }
+        )*
+    };
+}
+
+impl<'a, 'w, W: DigestWriter, B: ByteOrder> Serializer for &'a mut DigestSerializer<'w, W, B> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Compound<'a, 'w, W, B>;
+    type SerializeTuple = Compound<'a, 'w, W, B>;
+    type SerializeTupleStruct = Compound<'a, 'w, W, B>;
+    type SerializeTupleVariant = Compound<'a, 'w, W, B>;
+    type SerializeMap = Compound<'a, 'w, W, B>;
+    type SerializeStruct = Compound<'a, 'w, W, B>;
+    type SerializeStructVariant = Compound<'a, 'w, W, B>;
+
+    fn serialize_bool(self, v: bool) -> Result<Self::Ok, Self::Error> {
+        self.writer.write_u8(tags::BOOL);
+        self.writer.write_bool(v);
+        Ok(())
+    }
+    fn serialize_i8(self, v: i8) -> Result<Self::Ok, Self::Error> {
+        self.writer.write_u8(tags::I8);
+        self.writer.write_i8(v);
+        Ok(())
+    }
+    fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
+        self.writer.write_u8(tags::U8);
+        self.writer.write_u8(v);
+        Ok(())
+    }
+    serialize_num!(
+        serialize_i16(i16) => I16, write_i16,
+        serialize_i32(i32) => I32, write_i32,
+        serialize_i64(i64) => I64, write_i64,
+        serialize_i128(i128) => I128, write_i128,
+        serialize_u16(u16) => U16, write_u16,
+        serialize_u32(u32) => U32, write_u32,
+        serialize_u64(u64) => U64, write_u64,
+        serialize_u128(u128) => U128, write_u128,
+        serialize_f32(f32) => F32, write_f32,
+        serialize_f64(f64) => F64, write_f64
+    );
+    fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error> {
+        self.writer.write_u8(tags::CHAR);
+        self.writer.write_u32::<B>(v as u32);
+        Ok(())
+    }
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
+        self.writer.write_u8(tags::STR);
+        self.write_str(v);
+        Ok(())
+    }
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        self.writer.write_u8(tags::BYTES);
+        self.write_bytes(v);
+        Ok(())
+    }
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        self.writer.write_u8(tags::NONE);
+        Ok(())
+    }
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<Self::Ok, Self::Error> {
+        self.writer.write_u8(tags::SOME);
+        value.serialize(self)
+    }
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        self.writer.write_u8(tags::UNIT);
+        Ok(())
+    }
+    fn serialize_unit_struct(self, name: &'static str) -> Result<Self::Ok, Self::Error> {
+        self.writer.write_u8(tags::UNIT_STRUCT);
+        self.write_name(name);
+        Ok(())
+    }
+    fn serialize_unit_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.write_variant(tags::UNIT_VARIANT, name, variant_index, variant);
+        Ok(())
+    }
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.writer.write_u8(tags::NEWTYPE_STRUCT);
+        self.write_name(name);
+        value.serialize(self)
+    }
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.write_variant(tags::NEWTYPE_VARIANT, name, variant_index, variant);
+        value.serialize(self)
+    }
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        self.writer.write_u8(tags::SEQ);
+        Ok(Compound::new(self))
+    }
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        self.writer.write_u8(tags::TUPLE);
+        self.writer.write_u64::<B>(len as u64);
+        Ok(Compound::new(self))
+    }
+    fn serialize_tuple_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        self.writer.write_u8(tags::TUPLE_STRUCT);
+        self.write_name(name);
+        self.writer.write_u64::<B>(len as u64);
+        Ok(Compound::new(self))
+    }
+    fn serialize_tuple_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        self.write_variant(tags::TUPLE_VARIANT, name, variant_index, variant);
+        self.writer.write_u64::<B>(len as u64);
+        Ok(Compound::new(self))
+    }
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        self.writer.write_u8(tags::MAP);
+        Ok(Compound::new(self))
+    }
+    fn serialize_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        self.writer.write_u8(tags::STRUCT);
+        self.write_name(name);
+        self.writer.write_u64::<B>(len as u64);
+        Ok(Compound::new(self))
+    }
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        self.write_variant(tags::STRUCT_VARIANT, name, variant_index, variant);
+        self.writer.write_u64::<B>(len as u64);
+        Ok(Compound::new(self))
+    }
+}
+
+/// Serializes the elements of sequences, tuples, maps and structs
+#[doc(hidden)]
+pub struct Compound<'a, 'w, W: DigestWriter, B: ByteOrder> {
+    serializer: &'a mut DigestSerializer<'w, W, B>,
+    len: u64,
+}
+impl<'a, 'w, W: DigestWriter, B: ByteOrder> Compound<'a, 'w, W, B> {
+    fn new(serializer: &'a mut DigestSerializer<'w, W, B>) -> Self {
+        Self { serializer, len: 0 }
+    }
+    #[inline]
+    fn element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.len += 1;
+        value.serialize(&mut *self.serializer)
+    }
+    #[inline]
+    fn field<T: ?Sized + Serialize>(&mut self, key: &'static str, value: &T) -> Result<(), Error> {
+        self.serializer.write_name(key);
+        value.serialize(&mut *self.serializer)
+    }
+    /// Writes the end tag and the number of elements
+    #[inline]
+    fn end_counted(self) -> Result<(), Error> {
+        self.serializer.writer.write_u8(tags::END);
+        self.serializer.writer.write_u64::<B>(self.len);
+        Ok(())
+    }
+}
+impl<W: DigestWriter, B: ByteOrder> SerializeSeq for Compound<'_, '_, W, B> {
+    type Ok = ();
+    type Error = Error;
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+    fn end(self) -> Result<(), Error> {
+        self.end_counted()
+    }
+}
+impl<W: DigestWriter, B: ByteOrder> SerializeTuple for Compound<'_, '_, W, B> {
+    type Ok = ();
+    type Error = Error;
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+impl<W: DigestWriter, B: ByteOrder> SerializeTupleStruct for Compound<'_, '_, W, B> {
+    type Ok = ();
+    type Error = Error;
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+impl<W: DigestWriter, B: ByteOrder> SerializeTupleVariant for Compound<'_, '_, W, B> {
+    type Ok = ();
+    type Error = Error;
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+impl<W: DigestWriter, B: ByteOrder> SerializeMap for Compound<'_, '_, W, B> {
+    type Ok = ();
+    type Error = Error;
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), Error> {
+        self.element(key)
+    }
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        value.serialize(&mut *self.serializer)
+    }
+    fn end(self) -> Result<(), Error> {
+        self.end_counted()
+    }
+}
+impl<W: DigestWriter, B: ByteOrder> SerializeStruct for Compound<'_, '_, W, B> {
+    type Ok = ();
+    type Error = Error;
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.field(key, value)
+    }
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+impl<W: DigestWriter, B: ByteOrder> SerializeStructVariant for Compound<'_, '_, W, B> {
+    type Ok = ();
+    type Error = Error;
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.field(key, value)
+    }
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// Digests the inner value with its [Serialize] implementation. Names are not written.
+///
+/// # Panics
+/// If the [Serialize] implementation returns an error.
+/// Use [try_digest_serialize](crate::digest_with::try_digest_serialize) to handle the error
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SerdeDigest<T>(pub T);
+impl<T: Serialize> Digestible for SerdeDigest<T> {
+    fn digest<B: ByteOrder, W: DigestWriter>(&self, writer: &mut W) {
+        crate::digest_with::digest_serialize::<B, W>(&self.0, writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{tags, DigestSerializer, SerdeDigest};
+    use crate::Digestible;
+    use alloc::collections::BTreeMap;
+    use byteorder::BigEndian;
+    use serde::Serialize;
+
+    fn digest(value: &impl Serialize) -> Vec<u8> {
+        let mut digest = Vec::new();
+        SerdeDigest(value).digest::<BigEndian, _>(&mut digest);
+        digest
+    }
+
+    #[test]
+    fn primitives() {
+        assert_eq!(digest(&1u16), vec![tags::U16, 0, 1]);
+        assert_eq!(digest(&Some(true)), vec![tags::SOME, tags::BOOL, 1]);
+        assert_eq!(
+            digest(&"ab"),
+            [&[tags::STR][..], &2u64.to_be_bytes(), b"ab"].concat()
+        );
+    }
+
+    #[test]
+    fn framing() {
+        let nested: Vec<Vec<u8>> = vec![vec![1], vec![]];
+        let other: Vec<Vec<u8>> = vec![vec![], vec![1]];
+        assert_ne!(digest(&nested), digest(&other));
+
+        let mut map = BTreeMap::new();
+        map.insert("a", 1u8);
+        assert_eq!(
+            digest(&map),
+            [
+                &[tags::MAP, tags::STR][..],
+                &1u64.to_be_bytes(),
+                b"a",
+                &[tags::U8, 1, tags::END],
+                &1u64.to_be_bytes()
+            ]
+            .concat()
+        );
+    }
+
+    #[derive(Serialize)]
+    struct Point {
+        x: u8,
+        y: u8,
+    }
+
+    #[test]
+    fn names() {
+        let point = Point { x: 1, y: 2 };
+        assert_eq!(
+            digest(&point),
+            [
+                &[tags::STRUCT][..],
+                &2u64.to_be_bytes(),
+                &[tags::U8, 1, tags::U8, 2]
+            ]
+            .concat()
+        );
+
+        let mut named = Vec::new();
+        point
+            .serialize(&mut DigestSerializer::<_, BigEndian>::new(&mut named).with_names(true))
+            .unwrap();
+        assert_eq!(
+            named,
+            [
+                &[tags::STRUCT][..],
+                &5u64.to_be_bytes(),
+                b"Point",
+                &2u64.to_be_bytes(),
+                &1u64.to_be_bytes(),
+                b"x",
+                &[tags::U8, 1],
+                &1u64.to_be_bytes(),
+                b"y",
+                &[tags::U8, 2]
+            ]
+            .concat()
+        );
+    }
+}
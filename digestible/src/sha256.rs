@@ -0,0 +1,284 @@
+//! A SHA-256 implementation that can be used in const contexts.
+//!
+//! Used where a fixed 256 bit hash is needed without depending on a hash crate.
+//! Such as [schema](crate::schema) fingerprints. They are computed at compile time. So they need a const implementation.
+//!
+//! It is slow. It processes one byte at a time and copies its state on every call.
+//! Hashing at runtime goes through [Sha256Hasher]. It uses the `sha2` crate if the `sha2` feature is enabled.
+//! Such as [multiset](crate::multiset) element hashes and the HMAC of [keyed](crate::keyed) fields
+const K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+const INITIAL_STATE: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// The state of a SHA-256 hash. Every method takes and returns the state by value
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Sha256 {
+    state: [u32; 8],
+    block: [u8; 64],
+    block_len: usize,
+    total_len: u64,
+}
+impl Sha256 {
+    pub(crate) const fn new() -> Self {
+        Self {
+            state: INITIAL_STATE,
+            block: [0; 64],
+            block_len: 0,
+            total_len: 0,
+        }
+    }
+    pub(crate) const fn update(mut self, data: &[u8]) -> Self {
+        let mut index = 0;
+        while index < data.len() {
+            self.block[self.block_len] = data[index];
+            self.block_len += 1;
+            index += 1;
+            if self.block_len == 64 {
+                self.state = compress(self.state, &self.block);
+                self.block_len = 0;
+            }
+        }
+        self.total_len += data.len() as u64;
+        self
+    }
+    pub(crate) const fn finalize(mut self) -> [u8; 32] {
+        let bit_len = self.total_len.wrapping_mul(8);
+        self.block[self.block_len] = 0x80;
+        self.block_len += 1;
+        if self.block_len > 56 {
+            while self.block_len < 64 {
+                self.block[self.block_len] = 0;
+                self.block_len += 1;
+            }
+            self.state = compress(self.state, &self.block);
+            self.block_len = 0;
+        }
+        while self.block_len < 56 {
+            self.block[self.block_len] = 0;
+            self.block_len += 1;
+        }
+        let len_bytes = bit_len.to_be_bytes();
+        let mut index = 0;
+        while index < 8 {
+            self.block[56 + index] = len_bytes[index];
+            index += 1;
+        }
+        self.state = compress(self.state, &self.block);
+
+        let mut result = [0u8; 32];
+        let mut index = 0;
+        while index < 8 {
+            let bytes = self.state[index].to_be_bytes();
+            result[index * 4] = bytes[0];
+            result[index * 4 + 1] = bytes[1];
+            result[index * 4 + 2] = bytes[2];
+            result[index * 4 + 3] = bytes[3];
+            index += 1;
+        }
+        result
+    }
+}
+
+/// SHA-256 for hashing at runtime. See [module docs](self)
+#[cfg(feature = "sha2")]
+#[derive(Debug, Clone)]
+pub(crate) struct Sha256Hasher(sha2::Sha256);
+#[cfg(feature = "sha2")]
+impl Sha256Hasher {
+    pub(crate) fn new() -> Self {
+        Self(sha2::Digest::new())
+    }
+    #[inline(always)]
+    pub(crate) fn update(&mut self, data: &[u8]) {
+        sha2::Digest::update(&mut self.0, data);
+    }
+    pub(crate) fn finalize(self) -> [u8; 32] {
+        sha2::Digest::finalize(self.0).into()
+    }
+}
+/// SHA-256 for hashing at runtime. See [module docs](self)
+#[cfg(not(feature = "sha2"))]
+#[derive(Debug, Clone)]
+pub(crate) struct Sha256Hasher(Sha256);
+#[cfg(not(feature = "sha2"))]
+impl Sha256Hasher {
+    pub(crate) fn new() -> Self {
+        Self(Sha256::new())
+    }
+    #[inline(always)]
+    pub(crate) fn update(&mut self, data: &[u8]) {
+        self.0 = self.0.update(data);
+    }
+    pub(crate) fn finalize(self) -> [u8; 32] {
+        self.0.finalize()
+    }
+}
+impl Default for Sha256Hasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// HMAC-SHA256 using [Sha256Hasher]
+#[derive(Debug, Clone)]
+pub(crate) struct HmacSha256 {
+    inner: Sha256Hasher,
+    outer: Sha256Hasher,
+}
+impl HmacSha256 {
+    const BLOCK_SIZE: usize = 64;
+    pub(crate) fn new(key: &[u8]) -> Self {
+        let mut block = [0u8; Self::BLOCK_SIZE];
+        if key.len() > Self::BLOCK_SIZE {
+            let mut hasher = Sha256Hasher::new();
+            hasher.update(key);
+            block[..32].copy_from_slice(&hasher.finalize());
+        } else {
+            block[..key.len()].copy_from_slice(key);
+        }
+        let mut inner_pad = [0x36u8; Self::BLOCK_SIZE];
+        let mut outer_pad = [0x5cu8; Self::BLOCK_SIZE];
+        for ((inner, outer), key) in inner_pad.iter_mut().zip(&mut outer_pad).zip(block) {
+            *inner ^= key;
+            *outer ^= key;
+        }
+        let mut inner = Sha256Hasher::new();
+        inner.update(&inner_pad);
+        let mut outer = Sha256Hasher::new();
+        outer.update(&outer_pad);
+        Self { inner, outer }
+    }
+    #[inline(always)]
+    pub(crate) fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+    pub(crate) fn finalize(self) -> [u8; 32] {
+        let Self { inner, mut outer } = self;
+        outer.update(&inner.finalize());
+        outer.finalize()
+    }
+}
+
+const fn compress(mut state: [u32; 8], block: &[u8; 64]) -> [u32; 8] {
+    let mut w = [0u32; 64];
+    let mut index = 0;
+    while index < 16 {
+        w[index] = u32::from_be_bytes([
+            block[index * 4],
+            block[index * 4 + 1],
+            block[index * 4 + 2],
+            block[index * 4 + 3],
+        ]);
+        index += 1;
+    }
+    while index < 64 {
+        let s0 =
+            w[index - 15].rotate_right(7) ^ w[index - 15].rotate_right(18) ^ (w[index - 15] >> 3);
+        let s1 =
+            w[index - 2].rotate_right(17) ^ w[index - 2].rotate_right(19) ^ (w[index - 2] >> 10);
+        w[index] = w[index - 16]
+            .wrapping_add(s0)
+            .wrapping_add(w[index - 7])
+            .wrapping_add(s1);
+        index += 1;
+    }
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = state;
+    let mut index = 0;
+    while index < 64 {
+        let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+        let ch = (e & f) ^ (!e & g);
+        let temp1 = h
+            .wrapping_add(s1)
+            .wrapping_add(ch)
+            .wrapping_add(K[index])
+            .wrapping_add(w[index]);
+        let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+        let maj = (a & b) ^ (a & c) ^ (b & c);
+        let temp2 = s0.wrapping_add(maj);
+        h = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(temp1);
+        d = c;
+        c = b;
+        b = a;
+        a = temp1.wrapping_add(temp2);
+        index += 1;
+    }
+    state[0] = state[0].wrapping_add(a);
+    state[1] = state[1].wrapping_add(b);
+    state[2] = state[2].wrapping_add(c);
+    state[3] = state[3].wrapping_add(d);
+    state[4] = state[4].wrapping_add(e);
+    state[5] = state[5].wrapping_add(f);
+    state[6] = state[6].wrapping_add(g);
+    state[7] = state[7].wrapping_add(h);
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HmacSha256, Sha256, Sha256Hasher};
+    use sha2::Digest;
+
+    #[test]
+    fn matches_sha2() {
+        let data: Vec<u8> = (0..300u32).map(|v| v as u8).collect();
+        // Covers the padding with and without an extra block
+        for len in [0, 1, 55, 56, 63, 64, 65, 127, 128, 300] {
+            let expected: [u8; 32] = sha2::Sha256::digest(&data[..len]).into();
+            assert_eq!(Sha256::new().update(&data[..len]).finalize(), expected);
+        }
+        const ABC: [u8; 32] = Sha256::new().update(b"a").update(b"bc").finalize();
+        let expected: [u8; 32] = sha2::Sha256::digest(b"abc").into();
+        assert_eq!(ABC, expected);
+
+        let mut hasher = Sha256Hasher::new();
+        hasher.update(&data[..100]);
+        hasher.update(&data[100..]);
+        assert_eq!(hasher.finalize(), Sha256::new().update(&data).finalize());
+    }
+
+    #[test]
+    fn hmac() {
+        // RFC 4231 test cases 1, 2 and 6
+        fn hex(bytes: [u8; 32]) -> String {
+            bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+        }
+        let hmac = |key: &[u8], parts: &[&[u8]]| {
+            let mut mac = HmacSha256::new(key);
+            for part in parts {
+                mac.update(part);
+            }
+            mac.finalize()
+        };
+        let mac = hmac(&[0x0b; 20], &[b"Hi There"]);
+        assert_eq!(
+            hex(mac),
+            "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7"
+        );
+        let mac = hmac(b"Jefe", &[b"what do ya want ", b"for nothing?"]);
+        assert_eq!(
+            hex(mac),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        let mac = hmac(
+            &[0xaa; 131],
+            &[b"Test Using Larger Than Block-Size Key - Hash Key First"],
+        );
+        assert_eq!(
+            hex(mac),
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+        );
+    }
+}
@@ -0,0 +1,280 @@
+/*!
+# Signed Digests
+
+Signs the digest of a value instead of its serialized bytes. Requires the `signature` feature.
+
+Any signer and verifier of the [signature](signature_2) crate can be used. Such as ed25519 or ECDSA.
+Any [Digester] with a target that is bytes can be used.
+
+## Message
+The signed message is
+- The length of the algorithm ID as a u64 in little endian
+- The algorithm ID. Such as `sha256`
+- The [format](crate::format) as a u8. `0` for [Legacy](crate::format::Legacy) and `1` for [FormatV1]
+- The digest of the value with that format
+
+The algorithm ID and the format are signed. So the digest can not be verified with another algorithm or format.
+Pick an ID for every Digester and ByteOrder that is used.
+
+[Signed] uses [FormatV1] by default. Legacy does not frame values.
+So `vec!["a", "bc"]` and `vec!["ab", "c"]` have the same Legacy digest and one signature would verify both.
+
+## Example
+```rust
+use digestible::signed::Signed;
+use digestible::Digestible;
+use byteorder::LittleEndian;
+use ed25519_dalek::{Signature, SigningKey};
+use sha2::{Digest, Sha256};
+#[derive(Digestible)]
+pub struct Bundle {
+    pub version: u32,
+    pub files: Vec<String>,
+}
+let signing_key = SigningKey::from_bytes(&[7; 32]);
+let bundle = Bundle {
+    version: 1,
+    files: vec!["config.toml".to_string()],
+};
+let signed: Signed<Bundle, Signature> =
+    Signed::sign::<LittleEndian, _, _>(bundle, "sha256", Sha256::new(), &signing_key).unwrap();
+assert_eq!(signed.algorithm(), "sha256");
+signed
+    .verify::<LittleEndian, _, _>(Sha256::new(), &signing_key.verifying_key())
+    .unwrap();
+```
+*/
+use crate::format::{EncodingFormat, Format, FormatV1};
+use crate::{Digester, Digestible};
+use alloc::string::String;
+use alloc::vec::Vec;
+use byteorder::ByteOrder;
+use core::marker::PhantomData;
+use signature_2::{Error, Signer, Verifier};
+
+/// A value with a signature of its digest. See [module docs](self)
+///
+/// The value is digested with the [format](crate::format) `F`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Signed<T, S, F: EncodingFormat = FormatV1> {
+    value: T,
+    algorithm: String,
+    signature: S,
+    _format: PhantomData<F>,
+}
+impl<T: Digestible, S, F: EncodingFormat> Signed<T, S, F> {
+    /// Digests the value with the Digester and signs it
+    pub fn sign<B: ByteOrder, D, K>(
+        value: T,
+        algorithm: impl Into<String>,
+        digester: D,
+        signer: &K,
+    ) -> Result<Self, Error>
+    where
+        D: Digester,
+        D::Target: AsRef<[u8]>,
+        K: Signer<S>,
+    {
+        let algorithm = algorithm.into();
+        let signature = sign::<F, B, _, _, _>(&value, &algorithm, digester, signer)?;
+        Ok(Self::from_parts(value, algorithm, signature))
+    }
+    /// Digests the value with the Digester and verifies the signature
+    ///
+    /// The Digester must be the one of the [algorithm](Self::algorithm)
+    pub fn verify<B: ByteOrder, D, V>(&self, digester: D, verifier: &V) -> Result<(), Error>
+    where
+        D: Digester,
+        D::Target: AsRef<[u8]>,
+        V: Verifier<S>,
+    {
+        verify::<F, B, _, _, _>(
+            &self.value,
+            &self.algorithm,
+            digester,
+            &self.signature,
+            verifier,
+        )
+    }
+}
+impl<T, S, F: EncodingFormat> Signed<T, S, F> {
+    /// Creates it from parts. Such as after deserializing them. Nothing is verified
+    pub fn from_parts(value: T, algorithm: impl Into<String>, signature: S) -> Self {
+        Self {
+            value,
+            algorithm: algorithm.into(),
+            signature,
+            _format: PhantomData,
+        }
+    }
+    /// The value. It might not be verified
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+    /// The ID of the digest algorithm
+    pub fn algorithm(&self) -> &str {
+        &self.algorithm
+    }
+    /// The detached signature
+    pub fn signature(&self) -> &S {
+        &self.signature
+    }
+    /// Returns the value, the algorithm ID and the signature
+    pub fn into_parts(self) -> (T, String, S) {
+        (self.value, self.algorithm, self.signature)
+    }
+}
+
+/// Digests the value with the format `F` and signs the [message](self#message)
+pub fn sign<F: EncodingFormat, B: ByteOrder, D, K, S>(
+    value: &(impl Digestible + ?Sized),
+    algorithm: &str,
+    digester: D,
+    signer: &K,
+) -> Result<S, Error>
+where
+    D: Digester,
+    D::Target: AsRef<[u8]>,
+    K: Signer<S>,
+{
+    signer.try_sign(&message::<F, B, _>(value, algorithm, digester))
+}
+/// Digests the value with the format `F` and verifies the signature of the [message](self#message)
+pub fn verify<F: EncodingFormat, B: ByteOrder, D, V, S>(
+    value: &(impl Digestible + ?Sized),
+    algorithm: &str,
+    digester: D,
+    signature: &S,
+    verifier: &V,
+) -> Result<(), Error>
+where
+    D: Digester,
+    D::Target: AsRef<[u8]>,
+    V: Verifier<S>,
+{
+    verifier.verify(&message::<F, B, _>(value, algorithm, digester), signature)
+}
+/// The bytes that are signed. See [Message](self#message)
+pub fn message<F: EncodingFormat, B: ByteOrder, D>(
+    value: &(impl Digestible + ?Sized),
+    algorithm: &str,
+    digester: D,
+) -> Vec<u8>
+where
+    D: Digester,
+    D::Target: AsRef<[u8]>,
+{
+    let digest = digester.digest_v::<F, B>(&value);
+    let digest = digest.as_ref();
+    let format = match F::FORMAT {
+        Format::Legacy => 0,
+        Format::V1 => 1,
+    };
+    let mut message = Vec::with_capacity(9 + algorithm.len() + digest.len());
+    message.extend_from_slice(&(algorithm.len() as u64).to_le_bytes());
+    message.extend_from_slice(algorithm.as_bytes());
+    message.push(format);
+    message.extend_from_slice(digest);
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{message, Signed};
+    use crate::format::{FormatV1, Legacy};
+    use crate::Digester;
+    use byteorder::LittleEndian;
+    use ed25519_dalek::{Signature, SigningKey};
+    use sha2::{Digest, Sha256, Sha512};
+
+    /// Fixed keys so the tests do not need randomness
+    fn signing_key() -> SigningKey {
+        SigningKey::from_bytes(&[7; 32])
+    }
+
+    #[test]
+    fn sign_and_verify() {
+        let key = signing_key();
+        let signed: Signed<Vec<String>, Signature> = Signed::sign::<LittleEndian, _, _>(
+            vec!["a".to_string()],
+            "sha256",
+            Sha256::new(),
+            &key,
+        )
+        .unwrap();
+        let verifying_key = key.verifying_key();
+        signed
+            .verify::<LittleEndian, _, _>(Sha256::new(), &verifying_key)
+            .unwrap();
+
+        let expected =
+            message::<FormatV1, LittleEndian, _>(&vec!["a".to_string()], "sha256", Sha256::new());
+        assert_eq!(&expected[..8], &6u64.to_le_bytes());
+        assert_eq!(expected[14], 1);
+        assert_eq!(
+            &expected[15..],
+            Sha256::new()
+                .digest_v::<FormatV1, LittleEndian>(&vec!["a".to_string()])
+                .as_slice()
+        );
+        verifying_key
+            .verify_strict(&expected, signed.signature())
+            .unwrap();
+    }
+
+    #[test]
+    fn rejects_changes() {
+        let key = signing_key();
+        let verifying_key = key.verifying_key();
+        let signed: Signed<u32, Signature> =
+            Signed::sign::<LittleEndian, _, _>(1, "sha256", Sha256::new(), &key).unwrap();
+        let (value, algorithm, signature) = signed.into_parts();
+
+        let changed_value: Signed<u32, Signature> =
+            Signed::from_parts(2u32, algorithm.clone(), signature);
+        assert!(changed_value
+            .verify::<LittleEndian, _, _>(Sha256::new(), &verifying_key)
+            .is_err());
+        let changed_algorithm: Signed<u32, Signature> =
+            Signed::from_parts(value, "sha512", signature);
+        assert!(changed_algorithm
+            .verify::<LittleEndian, _, _>(Sha256::new(), &verifying_key)
+            .is_err());
+        let changed_format: Signed<u32, Signature, Legacy> =
+            Signed::from_parts(value, algorithm.clone(), signature);
+        assert!(changed_format
+            .verify::<LittleEndian, _, _>(Sha256::new(), &verifying_key)
+            .is_err());
+        let wrong_digester: Signed<u32, Signature> =
+            Signed::from_parts(value, algorithm, signature);
+        assert!(wrong_digester
+            .verify::<LittleEndian, _, _>(Sha512::new(), &verifying_key)
+            .is_err());
+        let other_key = SigningKey::from_bytes(&[8; 32]).verifying_key();
+        assert!(wrong_digester
+            .verify::<LittleEndian, _, _>(Sha256::new(), &other_key)
+            .is_err());
+    }
+
+    #[test]
+    fn legacy_collisions_are_not_signed() {
+        let key = signing_key();
+        let a = vec!["a".to_string(), "bc".to_string()];
+        let b = vec!["ab".to_string(), "c".to_string()];
+        assert_eq!(
+            message::<Legacy, LittleEndian, _>(&a, "sha256", Sha256::new()),
+            message::<Legacy, LittleEndian, _>(&b, "sha256", Sha256::new())
+        );
+
+        let signed_a: Signed<Vec<String>, Signature> =
+            Signed::sign::<LittleEndian, _, _>(a, "sha256", Sha256::new(), &key).unwrap();
+        let signed_b: Signed<Vec<String>, Signature> =
+            Signed::sign::<LittleEndian, _, _>(b.clone(), "sha256", Sha256::new(), &key).unwrap();
+        assert_ne!(signed_a.signature(), signed_b.signature());
+        let (_, algorithm, signature) = signed_a.into_parts();
+        let forged: Signed<Vec<String>, Signature> = Signed::from_parts(b, algorithm, signature);
+        assert!(forged
+            .verify::<LittleEndian, _, _>(Sha256::new(), &key.verifying_key())
+            .is_err());
+    }
+}
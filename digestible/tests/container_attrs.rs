@@ -0,0 +1,141 @@
+use byteorder::{ByteOrder, LittleEndian};
+use digestible::{DigestWriter, Digestible};
+use uuid::Uuid;
+
+fn digest_bytes(value: &impl Digestible) -> Vec<u8> {
+    let mut writer = Vec::new();
+    value.digest::<LittleEndian, _>(&mut writer);
+    writer
+}
+
+#[derive(Digestible)]
+#[digestible(transparent)]
+pub struct UserId(Uuid);
+
+#[derive(Digestible)]
+#[digestible(transparent)]
+pub struct Named {
+    pub name: String,
+    #[digestible(skip)]
+    pub cached_len: usize,
+}
+
+#[test]
+pub fn transparent() {
+    let uuid = Uuid::new_v4();
+    assert_eq!(digest_bytes(&UserId(uuid)), digest_bytes(&uuid));
+
+    let named = Named {
+        name: "Test".to_string(),
+        cached_len: 4,
+    };
+    assert_eq!(digest_bytes(&named), digest_bytes(&"Test".to_string()));
+}
+
+#[derive(Digestible)]
+#[digestible(type_header = None)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+#[derive(Clone, Digestible)]
+#[digestible(into = "Point")]
+pub struct PointTuple(i32, i32);
+impl From<PointTuple> for Point {
+    fn from(value: PointTuple) -> Self {
+        Point {
+            x: value.0,
+            y: value.1,
+        }
+    }
+}
+
+#[derive(Digestible)]
+#[digestible(from_ref = Point)]
+pub struct PointArray([i32; 2]);
+impl From<&PointArray> for Point {
+    fn from(value: &PointArray) -> Self {
+        Point {
+            x: value.0[0],
+            y: value.0[1],
+        }
+    }
+}
+
+#[test]
+pub fn proxy() {
+    let point = Point { x: 1, y: 2 };
+    assert_eq!(digest_bytes(&PointTuple(1, 2)), digest_bytes(&point));
+    assert_eq!(digest_bytes(&PointArray([1, 2])), digest_bytes(&point));
+}
+
+mod framework {
+    pub use digestible as reexported;
+}
+
+#[derive(Digestible)]
+#[digestible(crate = "crate::framework::reexported", hash)]
+pub struct CustomCratePath {
+    pub id: u32,
+}
+
+#[test]
+pub fn custom_crate_path() {
+    let mut expected = digest_bytes(&core::any::type_name::<CustomCratePath>());
+    expected.extend_from_slice(&1u32.to_le_bytes());
+    assert_eq!(digest_bytes(&CustomCratePath { id: 1 }), expected);
+}
+
+#[derive(Clone, Copy, Digestible)]
+#[digestible(bytes)]
+#[repr(C)]
+pub struct RawHeader {
+    pub magic: [u8; 4],
+    pub version: u32,
+}
+
+#[derive(Clone, Copy, Digestible)]
+#[digestible(bytes)]
+#[repr(C)]
+pub union RawValue {
+    pub int: u64,
+    pub float: f64,
+}
+
+fn digest_union_int<B: ByteOrder, W: DigestWriter>(value: &IntOrFloat, writer: &mut W) {
+    if value.is_int {
+        writer.write_u64::<B>(unsafe { value.value.int });
+    } else {
+        writer.write_f64::<B>(unsafe { value.value.float });
+    }
+}
+#[derive(Digestible)]
+#[digestible(with = digest_union_int)]
+pub struct IntOrFloat {
+    pub is_int: bool,
+    pub value: RawValue,
+}
+
+#[test]
+pub fn raw_bytes() {
+    let header = RawHeader {
+        magic: *b"TEST",
+        version: 1,
+    };
+    let mut expected = b"TEST".to_vec();
+    expected.extend_from_slice(&1u32.to_ne_bytes());
+    assert_eq!(digest_bytes(&header), expected);
+
+    let value = RawValue { int: 5 };
+    assert_eq!(digest_bytes(&value), 5u64.to_ne_bytes());
+}
+
+#[test]
+pub fn container_with() {
+    let value = IntOrFloat {
+        is_int: true,
+        value: RawValue { int: 5 },
+    };
+    assert_eq!(digest_bytes(&value), 5u64.to_le_bytes());
+}
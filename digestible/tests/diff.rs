@@ -0,0 +1,122 @@
+use digestible::diff::{diff, Difference, DifferenceKind, DigestTree};
+use digestible::format::{FormatV1, FormatWriter};
+use digestible::Digestible;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+
+#[derive(Digestible, Clone)]
+pub struct Item {
+    pub name: String,
+    pub price: u32,
+}
+#[derive(Digestible, Clone)]
+pub struct Point(pub i32, pub i32);
+#[derive(Digestible, Clone)]
+pub enum Status {
+    Open { since: u64 },
+    Closed(String),
+}
+#[derive(Digestible, Clone)]
+pub struct Order {
+    pub items: Vec<Item>,
+    pub metadata: HashMap<String, String>,
+    pub tags: HashSet<String>,
+    pub location: Point,
+    pub status: Status,
+    pub counts: HashMap<u32, u32>,
+}
+fn order() -> Order {
+    Order {
+        items: vec![
+            Item {
+                name: "apple".to_string(),
+                price: 1,
+            },
+            Item {
+                name: "pear".to_string(),
+                price: 2,
+            },
+        ],
+        metadata: HashMap::from([
+            ("region".to_string(), "eu".to_string()),
+            ("customer".to_string(), "42".to_string()),
+        ]),
+        tags: HashSet::from(["new".to_string()]),
+        location: Point(1, 2),
+        status: Status::Open { since: 10 },
+        counts: HashMap::from([(42, 1)]),
+    }
+}
+fn changed(path: &str) -> Difference {
+    Difference::new(path, DifferenceKind::Changed)
+}
+
+#[test]
+fn same_value() {
+    assert!(diff(&order(), &order()).is_empty());
+}
+
+#[test]
+fn changed_paths() {
+    let before = order();
+    let mut after = order();
+    after.items[1].price = 3;
+    after
+        .metadata
+        .insert("region".to_string(), "us".to_string());
+    after.location.1 = 5;
+    after.status = Status::Open { since: 11 };
+    after.counts.insert(42, 2);
+    assert_eq!(
+        diff(&before, &after),
+        vec![
+            changed("counts[0x2a000000]"),
+            changed("items[1].price"),
+            changed("location.1"),
+            changed("metadata[\"region\"]"),
+            changed("status.since"),
+        ]
+    );
+}
+
+#[test]
+fn added_and_removed() {
+    let before = order();
+    let mut after = order();
+    after.items.push(Item {
+        name: "plum".to_string(),
+        price: 3,
+    });
+    after.metadata.remove("customer");
+    after.tags.insert("sale".to_string());
+    after.status = Status::Closed("done".to_string());
+    assert_eq!(
+        diff(&before, &after),
+        vec![
+            Difference::new("items[2]", DifferenceKind::Added),
+            Difference::new("metadata[\"customer\"]", DifferenceKind::Removed),
+            Difference::new("status.0", DifferenceKind::Added),
+            Difference::new("status.since", DifferenceKind::Removed),
+            Difference::new("tags[\"sale\"]", DifferenceKind::Added),
+        ]
+    );
+    assert_eq!(
+        diff(&before, &after)[0].to_string(),
+        "added items[2]".to_string()
+    );
+}
+
+#[test]
+fn root() {
+    assert_eq!(diff(&1u32, &2u32), vec![changed("")]);
+    assert_eq!(changed("").to_string(), "changed <root>");
+
+    let order = order();
+    let tree = DigestTree::new(&order);
+    let mut bytes = FormatWriter::<FormatV1, _>::new(Vec::new());
+    order.digest::<byteorder::LittleEndian, _>(&mut bytes);
+    let expected: [u8; 32] = Sha256::digest(bytes.into_inner()).into();
+    assert_eq!(tree.root(), &expected);
+    assert!(tree.get("items[0].name").is_some());
+    assert!(tree.get("tags[\"new\"]").is_some());
+}
@@ -0,0 +1,64 @@
+use byteorder::{BigEndian, LittleEndian};
+use digestible::Digestible;
+
+fn digest_bytes(value: &impl Digestible) -> Vec<u8> {
+    let mut writer = Vec::new();
+    value.digest::<LittleEndian, _>(&mut writer);
+    writer
+}
+
+#[derive(Digestible)]
+#[digestible(type_header = None)]
+pub struct Packet {
+    #[digestible(endian = BigEndian)]
+    pub port: u16,
+    pub len: u32,
+}
+
+#[derive(Digestible)]
+#[digestible(type_header = None, endian = BigEndian)]
+pub struct NetworkFrame {
+    pub id: u16,
+    pub packet: Packet,
+    #[digestible(endian = LittleEndian)]
+    pub payload_len: u32,
+}
+
+#[derive(Digestible)]
+#[digestible(type_header = None)]
+pub enum Message {
+    Ping {
+        #[digestible(endian = BigEndian)]
+        id: u16,
+    },
+}
+
+#[test]
+pub fn field_endian() {
+    let packet = Packet { port: 80, len: 1 };
+    let mut expected = 80u16.to_be_bytes().to_vec();
+    expected.extend_from_slice(&1u32.to_le_bytes());
+    assert_eq!(digest_bytes(&packet), expected);
+
+    let mut expected = b"Ping".to_vec();
+    expected.extend_from_slice(&1u16.to_be_bytes());
+    assert_eq!(digest_bytes(&Message::Ping { id: 1 }), expected);
+}
+
+#[test]
+pub fn container_endian() {
+    let frame = NetworkFrame {
+        id: 1,
+        packet: Packet { port: 80, len: 2 },
+        payload_len: 3,
+    };
+    let mut expected = 1u16.to_be_bytes().to_vec();
+    expected.extend_from_slice(&80u16.to_be_bytes());
+    expected.extend_from_slice(&2u32.to_be_bytes());
+    expected.extend_from_slice(&3u32.to_le_bytes());
+    assert_eq!(digest_bytes(&frame), expected);
+
+    let mut big_endian = Vec::new();
+    frame.digest::<BigEndian, _>(&mut big_endian);
+    assert_eq!(big_endian, expected);
+}
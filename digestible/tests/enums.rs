@@ -0,0 +1,98 @@
+use byteorder::{ByteOrder, LittleEndian};
+use digestible::{DigestWriter, Digestible};
+
+fn digest_bytes(value: &impl Digestible) -> Vec<u8> {
+    let mut writer = Vec::new();
+    value.digest::<LittleEndian, _>(&mut writer);
+    writer
+}
+
+fn digest_id<B: ByteOrder, W: DigestWriter>(value: &u32, writer: &mut W) {
+    writer.write_u64::<B>(*value as u64);
+}
+
+pub struct Wrapper(u16);
+impl AsRef<u16> for Wrapper {
+    fn as_ref(&self) -> &u16 {
+        &self.0
+    }
+}
+
+#[derive(Digestible)]
+#[digestible(type_header = None)]
+pub enum Message {
+    Struct {
+        #[digestible(with = digest_id)]
+        id: u32,
+        #[digestible(as_ref = u16)]
+        wrapped: Wrapper,
+        #[digestible(skip)]
+        skipped: u64,
+    },
+    Tuple(
+        #[digestible(with = digest_id)] u32,
+        #[digestible(skip)] u64,
+        #[digestible(as_ref = u16)] Wrapper,
+    ),
+    DigestWith(#[digestible(digest_with = digest_with_hash)] u32),
+    #[digestible(skip)]
+    SkippedStruct {
+        id: u32,
+    },
+    #[digestible(skip)]
+    SkippedTuple(u32, u64),
+    #[digestible(skip)]
+    SkippedUnit,
+}
+
+#[derive(Digestible)]
+#[digestible(type_header = None)]
+pub enum Generic<T> {
+    Value(#[digestible(endian = BigEndian)] T),
+    #[digestible(skip)]
+    Ignored(T),
+}
+
+#[test]
+pub fn field_attributes() {
+    let message = Message::Struct {
+        id: 1,
+        wrapped: Wrapper(2),
+        skipped: 3,
+    };
+    let mut expected = b"Struct".to_vec();
+    expected.extend_from_slice(&1u64.to_le_bytes());
+    expected.extend_from_slice(&2u16.to_le_bytes());
+    assert_eq!(digest_bytes(&message), expected);
+
+    let message = Message::Tuple(1, 3, Wrapper(2));
+    let mut expected = b"Tuple".to_vec();
+    expected.extend_from_slice(&1u64.to_le_bytes());
+    expected.extend_from_slice(&2u16.to_le_bytes());
+    assert_eq!(digest_bytes(&message), expected);
+}
+
+#[test]
+pub fn digest_with() {
+    let mut expected = b"DigestWith".to_vec();
+    expected.extend_from_slice(&1u32.to_ne_bytes());
+    assert_eq!(digest_bytes(&Message::DigestWith(1)), expected);
+}
+
+#[test]
+pub fn skipped_variants() {
+    assert_eq!(
+        digest_bytes(&Message::SkippedStruct { id: 1 }),
+        b"SkippedStruct"
+    );
+    assert_eq!(digest_bytes(&Message::SkippedTuple(1, 2)), b"SkippedTuple");
+    assert_eq!(digest_bytes(&Message::SkippedUnit), b"SkippedUnit");
+}
+
+#[test]
+pub fn generic_variants() {
+    let mut expected = b"Value".to_vec();
+    expected.extend_from_slice(&1u32.to_be_bytes());
+    assert_eq!(digest_bytes(&Generic::Value(1u32)), expected);
+    assert_eq!(digest_bytes(&Generic::Ignored(1u32)), b"Ignored");
+}
@@ -0,0 +1,215 @@
+//! Golden test vectors for the encoding formats.
+//!
+//! Every vector pins the exact bytes in Legacy and V1 using LittleEndian.
+//! If one of these fails the encoding changed. Legacy and V1 must never change.
+use byteorder::LittleEndian;
+use digestible::format::{FormatV1, FormatWriter, Legacy};
+use digestible::Digestible;
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque};
+use std::ffi::{CStr, CString, OsStr};
+use std::marker::PhantomData;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV6};
+use std::num::{NonZeroU16, Saturating, Wrapping};
+use std::ops::Bound;
+use std::path::Path;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, UNIX_EPOCH};
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+fn legacy(value: &impl Digestible) -> String {
+    let mut writer = FormatWriter::<Legacy, _>::new(Vec::new());
+    value.digest::<LittleEndian, _>(&mut writer);
+    let bytes = writer.into_inner();
+    // A plain writer is always Legacy
+    let mut plain = Vec::new();
+    value.digest::<LittleEndian, _>(&mut plain);
+    assert_eq!(bytes, plain);
+    hex(&bytes)
+}
+fn v1(value: &impl Digestible) -> String {
+    let mut writer = FormatWriter::<FormatV1, _>::new(Vec::new());
+    value.digest::<LittleEndian, _>(&mut writer);
+    hex(&writer.into_inner())
+}
+macro_rules! vectors {
+    ($($name:ident: $value:expr => $legacy:literal, $v1:literal;)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let value = $value;
+                assert_eq!(legacy(&value), $legacy, "Legacy");
+                assert_eq!(v1(&value), $v1, "V1");
+            }
+        )*
+    };
+}
+
+#[derive(Digestible)]
+#[digestible(type_header = None)]
+struct Point {
+    x: u16,
+    label: String,
+}
+#[derive(Digestible)]
+struct Named {
+    x: u8,
+}
+#[derive(Digestible)]
+enum Shape {
+    Dot,
+    Line(u8, u8),
+}
+#[derive(Digestible)]
+#[digestible(type_header = None)]
+enum Plain {
+    Some { value: Option<u8> },
+}
+
+vectors! {
+    u8: 0xABu8 => "ab", "ab";
+    u16: 0x0102u16 => "0201", "0201";
+    u32: 0x0102_0304u32 => "04030201", "04030201";
+    u64: 1u64 => "0100000000000000", "0100000000000000";
+    u128: 1u128 => "01000000000000000000000000000000", "01000000000000000000000000000000";
+    i8: -1i8 => "ff", "ff";
+    i16: -2i16 => "feff", "feff";
+    i32: -2i32 => "feffffff", "feffffff";
+    i64: -2i64 => "feffffffffffffff", "feffffffffffffff";
+    i128: -1i128 => "ffffffffffffffffffffffffffffffff", "ffffffffffffffffffffffffffffffff";
+    f32: 1.5f32 => "0000c03f", "0000c03f";
+    f64: 1.5f64 => "000000000000f83f", "000000000000f83f";
+    f32_nan: f32::from_bits(0xFFC0_0001) => "0100c0ff", "0000c07f";
+    f64_nan: -f64::NAN => "000000000000f8ff", "000000000000f87f";
+    bool: true => "01", "01";
+    char: 'é' => "e9", "e9000000";
+    str: "hi" => "6869", "02000000000000006869";
+    string: String::from("hi") => "6869", "02000000000000006869";
+    slice: &[1u8, 2][..] => "0102", "02000000000000000102";
+    vec: vec![1u16, 2] => "01000200", "020000000000000001000200";
+    vec_deque: VecDeque::from([1u8, 2]) => "0102", "02000000000000000102";
+    linked_list: LinkedList::from([1u8, 2]) => "0102", "02000000000000000102";
+    b_tree_set: BTreeSet::from([2u8, 1]) => "0102", "02000000000000000102";
+    b_tree_map: BTreeMap::from([(1u8, "a")]) => "0161", "010000000000000001010000000000000061";
+    binary_heap: BinaryHeap::from([1u8, 2]) => "0201", "02000000000000000102";
+    hash_set: HashSet::from([2u8, 1]) => "0102", "02000000000000000102";
+    hash_map: HashMap::from([(2u8, 'b'), (1, 'a')]) => "01610262", "020000000000000001610000000262000000";
+    option_some: Some(1u8) => "01", "0101";
+    option_none: None::<u8> => "", "00";
+    result_ok: Ok::<u8, u8>(1) => "01", "0001";
+    result_err: Err::<u8, u8>(1) => "01", "0101";
+    tuple: (1u8, "a", 2u16) => "01610200", "010100000000000000610200";
+    tuple_1: (1u8,) => "01", "01";
+    tuple_7: (1u8, 2u8, 3u8, 4u8, 5u8, 6u8, "a") => "01020304050661", "010203040506010000000000000061";
+    tuple_16: (1u8, 2u8, 3u8, 4u8, 5u8, 6u8, 7u8, 8u8, 9u8, 10u8, 11u8, 12u8, 13u8, 14u8, 15u8, "a") => "0102030405060708090a0b0c0d0e0f61", "0102030405060708090a0b0c0d0e0f010000000000000061";
+    boxed: Box::new(1u8) => "01", "01";
+    rc: Rc::new(1u8) => "01", "01";
+    arc: Arc::new(1u8) => "01", "01";
+    cow: Cow::<u8>::Owned(1) => "01", "01";
+    weak: Rc::downgrade(&Rc::new(1u8)) => "", "00";
+    non_zero: NonZeroU16::new(1).unwrap() => "0100", "0100";
+    wrapping: Wrapping(1u8) => "01", "01";
+    saturating: Saturating(1u8) => "01", "01";
+    reverse: Reverse(1u8) => "01", "01";
+    ordering: Ordering::Less => "ff", "ff";
+    cell: Cell::new(1u8) => "01", "01";
+    ref_cell: RefCell::new(1u8) => "01", "01";
+    mutex: Mutex::new(1u8) => "01", "01";
+    rw_lock: RwLock::new(1u8) => "01", "01";
+    range: 1u8..2 => "0102", "0102";
+    range_inclusive: 1u8..=2 => "0102", "010102";
+    range_from: 1u8.. => "01", "01";
+    range_to: ..2u8 => "02", "02";
+    range_to_inclusive: ..=2u8 => "02", "0102";
+    range_full: .. => "", "";
+    bound: Bound::Excluded(1u8) => "0101", "0101";
+    phantom: PhantomData::<u8> => "", "";
+    c_str: CStr::from_bytes_with_nul(b"hi\0").unwrap() => "6869", "02000000000000006869";
+    c_string: CString::new("hi").unwrap() => "6869", "02000000000000006869";
+    os_str: OsStr::new("hi") => "6869", "02000000000000006869";
+    path: Path::new("a//b/") => "612f62", "0300000000000000612f62";
+    ipv4: Ipv4Addr::new(127, 0, 0, 1) => "7f000001", "7f000001";
+    ipv6: Ipv6Addr::LOCALHOST => "00000000000000000000000000000001", "00000000000000000000000000000001";
+    ip: IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)) => "047f000001", "047f000001";
+    socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 80) => "047f0000015000", "047f0000015000";
+    socket_v6: SocketAddrV6::new(Ipv6Addr::LOCALHOST, 80, 1, 2) => "0000000000000000000000000000000150000100000002000000", "0000000000000000000000000000000150000100000002000000";
+    system_time: UNIX_EPOCH + Duration::new(1, 2) => "010000000000000002000000", "010000000000000002000000";
+    duration: Duration::new(1, 2) => "010000000000000002000000", "010000000000000002000000";
+    derive_struct: Point { x: 1, label: "a".to_string() } => "010061", "0100010000000000000061";
+    derive_type_header: Named { x: 1 } => "666f726d61745f766563746f72733a3a4e616d656401", "1500000000000000666f726d61745f766563746f72733a3a4e616d656401";
+    derive_unit_variant: Shape::Dot => "666f726d61745f766563746f72733a3a53686170653a3a446f74", "1500000000000000666f726d61745f766563746f72733a3a53686170650300000000000000446f74";
+    derive_tuple_variant: Shape::Line(1, 2) => "666f726d61745f766563746f72733a3a53686170653a3a4c696e650102", "1500000000000000666f726d61745f766563746f72733a3a536861706504000000000000004c696e650102";
+    derive_struct_variant: Plain::Some { value: None } => "536f6d65", "0400000000000000536f6d6500";
+}
+
+#[test]
+fn digest_v() {
+    use digestible::Digester;
+    use sha2::{Digest, Sha256};
+    let value = vec!["a", "b"];
+    let mut writer = FormatWriter::<FormatV1, _>::new(Vec::new());
+    value.digest::<LittleEndian, _>(&mut writer);
+    assert_eq!(
+        Sha256::new().digest_v::<FormatV1, LittleEndian>(&value),
+        <Sha256 as Digest>::digest(writer.into_inner())
+    );
+    assert_eq!(
+        Sha256::new().digest_v::<Legacy, LittleEndian>(&value),
+        Sha256::new().digest::<LittleEndian>(&value)
+    );
+}
+
+#[cfg(feature = "uuid")]
+vectors! {
+    uuid: uuid::Uuid::from_u128(1) => "00000000000000000000000000000001", "00000000000000000000000000000001";
+}
+#[cfg(feature = "bytes")]
+vectors! {
+    bytes: bytes::Bytes::from_static(b"hi") => "6869", "02000000000000006869";
+}
+#[cfg(feature = "serde_json")]
+vectors! {
+    json: serde_json::json!({"a": [null, true]}) => "610001", "07010000000000000005010000000000000061060200000000000000000101";
+}
+#[cfg(feature = "chrono")]
+fn chrono_naive() -> chrono::NaiveDateTime {
+    chrono::NaiveDate::from_ymd_opt(1970, 1, 2)
+        .unwrap()
+        .and_hms_nano_opt(0, 0, 1, 2)
+        .unwrap()
+}
+#[cfg(feature = "chrono")]
+vectors! {
+    chrono_naive_date_time: chrono_naive() => "815101000000000002000000", "815101000000000002000000";
+    chrono_date_time: chrono::TimeZone::from_utc_datetime(&chrono::FixedOffset::east_opt(3600).unwrap(), &chrono_naive()) => "815101000000000002000000100e0000", "815101000000000002000000100e0000";
+    chrono_duration: chrono::TimeDelta::new(-1, 2).unwrap() => "0000000000000000023665c4", "0000000000000000023665c4";
+    chrono_naive_date: chrono::NaiveDate::from_ymd_opt(1, 1, 2).unwrap() => "02000000", "02000000";
+    chrono_naive_time: chrono::NaiveTime::from_hms_nano_opt(0, 0, 1, 2).unwrap() => "0100000002000000", "0100000002000000";
+    chrono_weekday: chrono::Weekday::Wed => "02", "02";
+    chrono_month: chrono::Month::March => "02", "02";
+    chrono_days: chrono::Days::new(3) => "0300000000000000", "0300000000000000";
+}
+#[cfg(feature = "time")]
+fn time_date_time() -> time::PrimitiveDateTime {
+    time::PrimitiveDateTime::new(
+        time::Date::from_calendar_date(1970, time::Month::January, 2).unwrap(),
+        time::Time::from_hms_nano(0, 0, 1, 2).unwrap(),
+    )
+}
+// The same values as the chrono vectors
+#[cfg(feature = "time")]
+vectors! {
+    time_primitive_date_time: time_date_time() => "815101000000000002000000", "815101000000000002000000";
+    time_offset_date_time: time_date_time().assume_utc().to_offset(time::UtcOffset::from_whole_seconds(3600).unwrap()) => "815101000000000002000000100e0000", "815101000000000002000000100e0000";
+    time_duration: time::Duration::new(-1, 2) => "0000000000000000023665c4", "0000000000000000023665c4";
+    time_date: time::Date::from_calendar_date(1, time::Month::January, 2).unwrap() => "02000000", "02000000";
+    time_time: time::Time::from_hms_nano(0, 0, 1, 2).unwrap() => "0100000002000000", "0100000002000000";
+    time_utc_offset: time::UtcOffset::from_whole_seconds(3600).unwrap() => "100e0000", "100e0000";
+    time_weekday: time::Weekday::Wednesday => "02", "02";
+    time_month: time::Month::March => "02", "02";
+}
@@ -0,0 +1,158 @@
+use byteorder::LittleEndian;
+use digestible::cached::Cached;
+use digestible::digest_with::unordered;
+use digestible::format::{FormatV1, FormatWriter};
+use digestible::keyed::KeyedWriter;
+use digestible::{Digester, Digestible};
+use sha2::{Digest, Sha256};
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+#[derive(Digestible)]
+#[digestible(type_header = None)]
+pub struct User {
+    pub id: u32,
+    #[digestible(salted)]
+    pub email: String,
+    #[digestible(keyed = "name")]
+    pub name: String,
+    #[digestible(redact)]
+    pub phone: Option<String>,
+    #[digestible(redact)]
+    pub address: String,
+}
+fn user(phone: Option<&str>) -> User {
+    User {
+        id: 7,
+        email: "user@example.com".to_string(),
+        name: "User".to_string(),
+        phone: phone.map(str::to_string),
+        address: "Street 1".to_string(),
+    }
+}
+fn hmac(key: &[u8], context: &str, field: &[u8]) -> Vec<u8> {
+    // HMAC-SHA256 from the RFC 2104 definition
+    let mut block = [0u8; 64];
+    block[..key.len()].copy_from_slice(key);
+    let inner_pad: Vec<u8> = block.iter().map(|byte| byte ^ 0x36).collect();
+    let outer_pad: Vec<u8> = block.iter().map(|byte| byte ^ 0x5c).collect();
+    let mut inner = Sha256::new();
+    inner.update(&inner_pad);
+    inner.update((context.len() as u64).to_le_bytes());
+    inner.update(context);
+    inner.update(field);
+    let mut outer = Sha256::new();
+    outer.update(&outer_pad);
+    outer.update(inner.finalize());
+    outer.finalize().to_vec()
+}
+
+#[test]
+fn with_key() {
+    let mut writer = KeyedWriter::new(Vec::new(), b"pepper");
+    user(Some("123")).digest::<LittleEndian, _>(&mut writer);
+    let mut expected = 7u32.to_le_bytes().to_vec();
+    expected.extend(hmac(b"pepper", "email", b"user@example.com"));
+    expected.extend(hmac(b"pepper", "name", b"User"));
+    expected.extend([1, 1]);
+    assert_eq!(writer.into_inner(), expected);
+
+    let mut writer = KeyedWriter::new(Vec::new(), b"pepper");
+    user(None).digest::<LittleEndian, _>(&mut writer);
+    assert_eq!(writer.into_inner()[68..], [0, 1]);
+}
+
+#[test]
+fn without_key() {
+    let mut digest = Vec::new();
+    user(Some("123")).digest::<LittleEndian, _>(&mut digest);
+    assert_eq!(digest, [7, 0, 0, 0, 1, 1, 1, 1]);
+}
+
+#[test]
+fn uses_format() {
+    let mut writer = KeyedWriter::new(FormatWriter::<FormatV1, _>::new(Vec::new()), b"pepper");
+    user(None).digest::<LittleEndian, _>(&mut writer);
+    let digest = writer.into_inner().into_inner();
+    let mut email = 16u64.to_le_bytes().to_vec();
+    email.extend(b"user@example.com");
+    assert_eq!(digest[4..36], hmac(b"pepper", "email", &email));
+}
+
+#[test]
+fn digest_keyed() {
+    let a = Sha256::new().digest_keyed::<LittleEndian>(b"pepper", &user(None));
+    let b = Sha256::new().digest_keyed::<LittleEndian>(b"salt", &user(None));
+    assert_ne!(a, b);
+    let mut writer = KeyedWriter::new(Vec::new(), b"pepper");
+    user(None).digest::<LittleEndian, _>(&mut writer);
+    assert_eq!(
+        a.as_slice(),
+        <Sha256 as Digest>::digest(writer.into_inner()).as_slice()
+    );
+}
+
+#[derive(Digestible, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[digestible(type_header = None)]
+pub struct Contact {
+    #[digestible(keyed = "email")]
+    pub email: String,
+}
+#[derive(Digestible)]
+#[digestible(type_header = None)]
+pub struct Contacts {
+    pub set: HashSet<Contact>,
+    pub map: HashMap<u8, Contact>,
+    pub heap: BinaryHeap<Contact>,
+    #[digestible(digest_with = unordered)]
+    pub unordered: Vec<Contact>,
+    pub cached: Cached<Contact, Sha256, LittleEndian>,
+}
+fn contacts(email: &str) -> Contacts {
+    let contact = || Contact {
+        email: email.to_string(),
+    };
+    Contacts {
+        set: HashSet::from([contact()]),
+        map: HashMap::from([(1, contact())]),
+        heap: BinaryHeap::from([contact()]),
+        unordered: vec![contact()],
+        cached: Cached::new(contact()),
+    }
+}
+fn keyed_field<T: Digestible>(value: &T) -> Vec<u8> {
+    let mut writer = KeyedWriter::new(FormatWriter::<FormatV1, _>::new(Vec::new()), b"pepper");
+    value.digest::<LittleEndian, _>(&mut writer);
+    writer.into_inner().into_inner()
+}
+
+#[test]
+fn collections_use_key() {
+    let a = contacts("a@x");
+    let b = contacts("b@x");
+    assert_ne!(keyed_field(&a.set), keyed_field(&b.set));
+    assert_ne!(keyed_field(&a.map), keyed_field(&b.map));
+    assert_ne!(keyed_field(&a.heap), keyed_field(&b.heap));
+    assert_ne!(keyed_field(&a.cached), keyed_field(&b.cached));
+    let unordered_field = |contacts: &Contacts| {
+        let mut writer = KeyedWriter::new(Vec::new(), b"pepper");
+        unordered::<LittleEndian, _>(&contacts.unordered, &mut writer);
+        writer.into_inner()
+    };
+    assert_ne!(unordered_field(&a), unordered_field(&b));
+    assert_ne!(
+        Sha256::new().digest_keyed::<LittleEndian>(b"pepper", &a),
+        Sha256::new().digest_keyed::<LittleEndian>(b"pepper", &b)
+    );
+
+    // A set of one item is the length then the item
+    let mut email = 3u64.to_le_bytes().to_vec();
+    email.extend(b"a@x");
+    let mut expected = 1u64.to_le_bytes().to_vec();
+    expected.extend(hmac(b"pepper", "email", &email));
+    assert_eq!(keyed_field(&a.set), expected);
+
+    // Without the key the cached digest is still used
+    let mut writer = Vec::new();
+    a.cached.digest::<LittleEndian, _>(&mut writer);
+    assert_eq!(writer, a.cached.cached_digest().as_slice());
+}
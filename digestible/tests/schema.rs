@@ -0,0 +1,150 @@
+#![allow(dead_code)]
+use digestible::schema::DigestSchema;
+use digestible::Digestible;
+
+mod before {
+    use digestible::Digestible;
+    #[derive(Digestible)]
+    #[digestible(schema)]
+    pub struct User {
+        pub id: u32,
+        pub name: String,
+        pub address: Address,
+    }
+    #[derive(Digestible)]
+    #[digestible(schema)]
+    pub struct Address {
+        pub street: String,
+    }
+    #[derive(Digestible)]
+    #[digestible(schema)]
+    pub enum Event {
+        Created { id: u32 },
+        Deleted(u32),
+    }
+}
+mod after {
+    use digestible::Digestible;
+    #[derive(Digestible)]
+    #[digestible(schema)]
+    pub struct User {
+        pub id: u32,
+        pub name: String,
+        pub address: Address,
+    }
+    /// Changed the field type
+    #[derive(Digestible)]
+    #[digestible(schema)]
+    pub struct Address {
+        pub street: Option<String>,
+    }
+    /// Added a variant
+    #[derive(Digestible)]
+    #[digestible(schema)]
+    pub enum Event {
+        Created { id: u32 },
+        Deleted(u32),
+        Restored,
+    }
+}
+mod same {
+    use digestible::Digestible;
+    use std::sync::Arc;
+    /// Wrappers are digested the same as the type inside them
+    #[derive(Digestible)]
+    #[digestible(schema)]
+    pub struct Address {
+        pub street: Arc<String>,
+    }
+}
+
+#[test]
+fn nested_changes() {
+    assert_ne!(
+        before::Address::SCHEMA_FINGERPRINT,
+        after::Address::SCHEMA_FINGERPRINT
+    );
+    // User did not change but the type of a field did
+    assert_ne!(
+        before::User::SCHEMA_FINGERPRINT,
+        after::User::SCHEMA_FINGERPRINT
+    );
+    assert_ne!(
+        before::Event::SCHEMA_FINGERPRINT,
+        after::Event::SCHEMA_FINGERPRINT
+    );
+    assert_eq!(
+        before::Address::SCHEMA_FINGERPRINT,
+        same::Address::SCHEMA_FINGERPRINT
+    );
+}
+
+#[derive(Digestible)]
+#[digestible(schema)]
+struct Renamed {
+    id: u32,
+}
+#[derive(Digestible)]
+#[digestible(schema)]
+struct Original {
+    id: u32,
+}
+#[derive(Digestible)]
+#[digestible(schema, type_header = None)]
+struct NoHeader {
+    id: u32,
+}
+#[derive(Digestible)]
+#[digestible(schema)]
+struct BigEndianField {
+    #[digestible(endian = BigEndian)]
+    id: u32,
+}
+#[derive(Digestible)]
+#[digestible(schema)]
+struct SkippedField {
+    #[digestible(skip)]
+    id: u32,
+}
+
+#[test]
+fn attributes_change() {
+    let fingerprints = [
+        Renamed::SCHEMA_FINGERPRINT,
+        Original::SCHEMA_FINGERPRINT,
+        NoHeader::SCHEMA_FINGERPRINT,
+        BigEndianField::SCHEMA_FINGERPRINT,
+        SkippedField::SCHEMA_FINGERPRINT,
+    ];
+    for (index, fingerprint) in fingerprints.iter().enumerate() {
+        for other in &fingerprints[index + 1..] {
+            assert_ne!(fingerprint, other);
+        }
+    }
+}
+
+#[derive(Digestible)]
+#[digestible(schema)]
+struct Wrapper<T> {
+    value: T,
+}
+#[derive(Digestible, Clone)]
+#[digestible(schema, into = "u64")]
+struct Proxy(u64);
+impl From<Proxy> for u64 {
+    fn from(value: Proxy) -> Self {
+        value.0
+    }
+}
+
+#[test]
+fn generics_and_proxies() {
+    assert_ne!(
+        Wrapper::<u32>::SCHEMA_FINGERPRINT,
+        Wrapper::<u64>::SCHEMA_FINGERPRINT
+    );
+    assert_ne!(Proxy::SCHEMA_FINGERPRINT, u64::SCHEMA_FINGERPRINT);
+    // The fingerprint is a constant
+    const FINGERPRINT: [u8; 32] = Wrapper::<Vec<u8>>::SCHEMA_FINGERPRINT;
+    assert_eq!(FINGERPRINT, Wrapper::<Vec<u8>>::SCHEMA_FINGERPRINT);
+}
@@ -0,0 +1,14 @@
+use digestible::byteorder::ByteOrder;
+use digestible::{DigestWriter, Digestible};
+
+fn digest_bytes<B: ByteOrder, W: DigestWriter>(value: &[u8], writer: &mut W) {
+    writer.write(value);
+}
+
+#[derive(Digestible)]
+pub struct Conflict {
+    #[digestible(as_ref = [u8], with = digest_bytes)]
+    pub data: Vec<u8>,
+}
+
+fn main() {}
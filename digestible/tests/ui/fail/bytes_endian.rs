@@ -0,0 +1,10 @@
+use digestible::Digestible;
+
+#[derive(Digestible, Clone, Copy)]
+#[digestible(bytes, endian = BigEndian)]
+#[repr(C)]
+pub struct Conflict {
+    pub id: u32,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use digestible::Digestible;
+
+#[derive(Digestible, Clone, Copy)]
+#[digestible(bytes)]
+#[repr(C)]
+pub enum Enum {
+    A,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use digestible::Digestible;
+
+#[derive(Digestible, Clone, Copy)]
+#[digestible(bytes)]
+pub struct NoRepr {
+    pub id: u32,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use digestible::Digestible;
+
+#[derive(Clone, Copy)]
+pub struct Inner(u32);
+
+#[derive(Digestible, Clone, Copy)]
+#[digestible(bytes)]
+#[repr(C)]
+pub struct Outer {
+    pub inner: Inner,
+}
+
+fn main() {}
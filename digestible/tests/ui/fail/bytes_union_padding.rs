@@ -0,0 +1,11 @@
+use digestible::Digestible;
+
+#[derive(Digestible, Clone, Copy)]
+#[digestible(bytes)]
+#[repr(C)]
+pub union Padding {
+    pub a: u8,
+    pub b: u32,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use digestible::Digestible;
+
+#[derive(Digestible)]
+#[digestible(type_header = None)]
+#[digestible(type_header = HashName)]
+pub struct Duplicate {
+    pub id: u32,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use digestible::Digestible;
+
+#[derive(Digestible)]
+pub struct Duplicate {
+    #[digestible(skip, skip)]
+    pub id: u32,
+}
+
+fn main() {}
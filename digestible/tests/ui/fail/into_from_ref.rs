@@ -0,0 +1,7 @@
+use digestible::Digestible;
+
+#[derive(Digestible, Clone)]
+#[digestible(into = u32, from_ref = u32)]
+pub struct Conflict(u32);
+
+fn main() {}
@@ -0,0 +1,9 @@
+use digestible::Digestible;
+
+#[derive(Digestible)]
+pub struct MissingComma {
+    #[digestible(skip endian = BigEndian)]
+    pub id: u32,
+}
+
+fn main() {}
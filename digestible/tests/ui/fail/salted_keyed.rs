@@ -0,0 +1,9 @@
+use digestible::Digestible;
+
+#[derive(Digestible)]
+pub struct Conflict {
+    #[digestible(salted, keyed = "email")]
+    pub email: String,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use digestible::Digestible;
+
+#[derive(Digestible)]
+pub struct Conflict {
+    #[digestible(skip, digest_with = digest_with_hash)]
+    pub id: u32,
+}
+
+fn main() {}
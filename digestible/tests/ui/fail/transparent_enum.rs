@@ -0,0 +1,9 @@
+use digestible::Digestible;
+
+#[derive(Digestible)]
+#[digestible(transparent)]
+pub enum Transparent {
+    A(u32),
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use digestible::Digestible;
+
+#[derive(Digestible)]
+#[digestible(transparent)]
+pub struct Transparent(u32, u32);
+
+fn main() {}
@@ -0,0 +1,7 @@
+use digestible::Digestible;
+
+#[derive(Digestible)]
+#[digestible(transparent, type_header = None)]
+pub struct Transparent(u32);
+
+fn main() {}
@@ -0,0 +1,9 @@
+use digestible::Digestible;
+
+#[derive(Digestible)]
+#[digestible(type_header = type_id)]
+pub struct TypeId {
+    pub id: u32,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use digestible::Digestible;
+
+#[derive(Digestible, Clone, Copy)]
+#[repr(C)]
+pub union Union {
+    pub a: u32,
+    pub b: f32,
+}
+
+fn main() {}
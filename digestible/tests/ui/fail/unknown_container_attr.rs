@@ -0,0 +1,9 @@
+use digestible::Digestible;
+
+#[derive(Digestible)]
+#[digestible(rename_all = "camelCase")]
+pub struct Unknown {
+    pub id: u32,
+}
+
+fn main() {}
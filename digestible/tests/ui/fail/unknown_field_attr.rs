@@ -0,0 +1,9 @@
+use digestible::Digestible;
+
+#[derive(Digestible)]
+pub struct Unknown {
+    #[digestible(rename = "id")]
+    pub id: u32,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use digestible::Digestible;
+
+#[derive(Digestible)]
+pub enum Unknown {
+    #[digestible(with = my_fn)]
+    A(u32),
+}
+
+fn main() {}
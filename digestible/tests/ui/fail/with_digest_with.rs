@@ -0,0 +1,10 @@
+use digestible::Digestible;
+
+#[derive(Digestible)]
+pub struct Conflict {
+    #[digestible(with = my_fn)]
+    #[digestible(digest_with = digest_with_hash)]
+    pub id: u32,
+}
+
+fn main() {}
@@ -0,0 +1,80 @@
+use digestible::byteorder::ByteOrder;
+use digestible::{DigestWriter, Digestible};
+
+#[derive(Digestible)]
+#[digestible(type_header = None, hash = LittleEndian)]
+pub struct NoHeader {
+    pub id: u32,
+}
+
+#[derive(Digestible)]
+#[digestible(type_header = HashName, hash)]
+pub enum WithHeader {
+    A,
+    B(u32),
+}
+
+#[derive(Digestible)]
+#[digestible(transparent)]
+pub struct Transparent(u32);
+
+#[derive(Digestible, Clone)]
+#[digestible(into = "u32")]
+pub struct Into(u32);
+impl From<Into> for u32 {
+    fn from(value: Into) -> Self {
+        value.0
+    }
+}
+
+#[derive(Digestible)]
+#[digestible(from_ref = u64)]
+pub struct FromRef(u32);
+impl From<&FromRef> for u64 {
+    fn from(value: &FromRef) -> Self {
+        value.0 as u64
+    }
+}
+
+fn digest_with<B: ByteOrder, W: DigestWriter>(value: &With, writer: &mut W) {
+    writer.write_u32::<B>(value.0);
+}
+#[derive(Digestible)]
+#[digestible(with = digest_with)]
+pub struct With(u32);
+
+#[derive(Digestible, Clone, Copy)]
+#[digestible(bytes)]
+#[repr(C)]
+pub struct Bytes {
+    pub a: u32,
+    pub b: [u8; 4],
+}
+
+#[derive(Digestible)]
+#[digestible(endian = BigEndian)]
+pub struct Endian {
+    pub id: u32,
+}
+
+mod framework {
+    pub use digestible as reexported;
+}
+#[derive(framework::reexported::Digestible)]
+#[digestible(crate = "framework::reexported")]
+pub struct CustomCrate {
+    pub id: u32,
+}
+
+#[derive(framework::reexported::Digestible)]
+#[digestible(crate = "framework::reexported", schema)]
+pub enum Schema<T> {
+    Value(T),
+    #[digestible(skip)]
+    Skipped(T),
+}
+
+fn main() {
+    let _: [u8; 32] =
+        <Schema<u32> as framework::reexported::schema::DigestSchema>::SCHEMA_FINGERPRINT;
+}
@@ -0,0 +1,46 @@
+use digestible::byteorder::ByteOrder;
+use digestible::{DigestWriter, Digestible};
+
+fn digest_id<B: ByteOrder, W: DigestWriter>(value: &u32, writer: &mut W) {
+    writer.write_u32::<B>(*value);
+}
+
+#[derive(Digestible)]
+pub struct Fields {
+    #[digestible(skip)]
+    pub skipped: u32,
+    #[digestible(with = digest_id)]
+    pub with: u32,
+    #[digestible(digest_with = digest_with_hash)]
+    pub digest_with: u32,
+    #[digestible(as_ref = u32)]
+    pub as_ref: Box<u32>,
+    #[digestible(endian = BigEndian)]
+    pub endian: u32,
+    #[digestible(with = digest_id, endian = LittleEndian)]
+    pub with_endian: u32,
+    #[digestible(redact)]
+    pub redacted: Option<String>,
+    #[digestible(salted)]
+    pub salted: String,
+    #[digestible(keyed = "id", with = digest_id)]
+    pub keyed: u32,
+}
+
+#[derive(Digestible)]
+pub enum Enum {
+    Tuple(#[digestible(skip)] u32, #[digestible(endian = BigEndian)] u32),
+    Secret(#[digestible(redact)] u32, #[digestible(salted)] String),
+    Struct {
+        #[digestible(with = digest_id)]
+        id: u32,
+        #[digestible(as_ref = u32)]
+        boxed: Box<u32>,
+        #[digestible(digest_with = digest_with_hash)]
+        hashed: u32,
+    },
+    #[digestible(skip)]
+    Skipped(u32),
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use digestible::Digestible;
+
+#[derive(Digestible)]
+#[digestible(type_header = None)]
+#[digestible(endian = BigEndian)]
+#[digestible(hash = LittleEndian)]
+pub struct Multiple {
+    #[digestible(as_ref = u32)]
+    #[digestible(endian = LittleEndian)]
+    pub data: Box<u32>,
+}
+
+fn main() {}
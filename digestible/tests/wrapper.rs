@@ -0,0 +1,109 @@
+//! A writer that wraps another writer must forward more than `write`
+use byteorder::LittleEndian;
+use digestible::format::{Format, FormatV1, FormatWriter};
+use digestible::keyed::KeyedWriter;
+use digestible::{DigestWriter, Digestible};
+
+/// Forwards every item listed in the DigestWriter docs
+struct Forwarding<W>(W);
+impl<W: DigestWriter> DigestWriter for Forwarding<W> {
+    const FORMAT: Format = W::FORMAT;
+    const TRACES_PATHS: bool = W::TRACES_PATHS;
+    fn write(&mut self, data: &[u8]) {
+        self.0.write(data)
+    }
+    fn enter_field(&mut self, name: &str) {
+        self.0.enter_field(name)
+    }
+    fn enter_index(&mut self, index: usize) {
+        self.0.enter_index(index)
+    }
+    fn enter_key<K: Digestible + ?Sized>(&mut self, key: &K) {
+        self.0.enter_key(key)
+    }
+    fn exit(&mut self) {
+        self.0.exit()
+    }
+    fn digest_key(&self) -> Option<&[u8]> {
+        self.0.digest_key()
+    }
+}
+/// Only forwards write
+struct WriteOnly<W>(W);
+impl<W: DigestWriter> DigestWriter for WriteOnly<W> {
+    fn write(&mut self, data: &[u8]) {
+        self.0.write(data)
+    }
+}
+/// Records the path hooks
+#[derive(Default)]
+struct Paths(Vec<String>);
+impl DigestWriter for Paths {
+    const TRACES_PATHS: bool = true;
+    fn write(&mut self, _: &[u8]) {}
+    fn enter_field(&mut self, name: &str) {
+        self.0.push(name.to_string());
+    }
+    fn enter_index(&mut self, index: usize) {
+        self.0.push(index.to_string());
+    }
+    fn exit(&mut self) {
+        self.0.push("exit".to_string());
+    }
+}
+
+#[derive(Digestible)]
+#[digestible(type_header = None)]
+pub struct Account {
+    pub tags: Vec<String>,
+    #[digestible(salted)]
+    pub email: String,
+}
+fn account() -> Account {
+    Account {
+        tags: vec!["a".to_string(), "bc".to_string()],
+        email: "user@example.com".to_string(),
+    }
+}
+fn keyed_v1() -> FormatWriter<FormatV1, KeyedWriter<'static, Vec<u8>>> {
+    FormatWriter::new(KeyedWriter::new(Vec::new(), b"pepper"))
+}
+
+#[test]
+fn forwarding_wrapper() {
+    let mut direct = keyed_v1();
+    account().digest::<LittleEndian, _>(&mut direct);
+    let mut wrapped = Forwarding(keyed_v1());
+    account().digest::<LittleEndian, _>(&mut wrapped);
+    assert_eq!(
+        wrapped.0.into_inner().into_inner(),
+        direct.into_inner().into_inner()
+    );
+
+    let mut paths = Forwarding(Paths::default());
+    account().digest::<LittleEndian, _>(&mut paths);
+    assert_eq!(
+        paths.0 .0,
+        ["tags", "0", "exit", "1", "exit", "exit", "email", "exit"]
+    );
+}
+
+#[test]
+fn write_only_wrapper_is_legacy() {
+    assert_eq!(
+        <WriteOnly<FormatWriter<FormatV1, Vec<u8>>>>::FORMAT,
+        Format::Legacy
+    );
+    let mut wrapped = WriteOnly(keyed_v1());
+    account().digest::<LittleEndian, _>(&mut wrapped);
+    let mut direct = keyed_v1();
+    account().digest::<LittleEndian, _>(&mut direct);
+    assert_ne!(
+        wrapped.0.into_inner().into_inner(),
+        direct.into_inner().into_inner()
+    );
+
+    let mut paths = WriteOnly(Paths::default());
+    account().digest::<LittleEndian, _>(&mut paths);
+    assert!(paths.0 .0.is_empty());
+}
@@ -1,20 +1,12 @@
 use syn::parse::{Parse, ParseStream};
 
-use syn::{parse_quote, Attribute, Path};
+use syn::{parse_quote, Attribute, Path, Type};
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub enum TypeHeader {
     None,
+    #[default]
     HashName,
-    #[allow(dead_code)]
-    TypeId {
-        path_to_type_id_gen: Path,
-    },
-}
-impl Default for TypeHeader {
-    fn default() -> Self {
-        Self::HashName
-    }
 }
 impl Parse for TypeHeader {
     fn parse(input: ParseStream) -> syn::Result<Self> {
@@ -26,12 +18,36 @@ impl Parse for TypeHeader {
             let _ = input.parse::<keywords::HashName>()?;
             Ok(Self::HashName)
         } else if lookahead.peek(keywords::type_id) {
-            todo!("type_id")
+            let type_id = input.parse::<keywords::type_id>()?;
+            Err(syn::Error::new(
+                type_id.span,
+                "`type_header = type_id` is not supported. TypeId is not stable between builds. Use `HashName` or `None`",
+            ))
         } else {
             Err(lookahead.error())
         }
     }
 }
+/// Replaces the field by field digest of the container.
+#[derive(Debug)]
+pub enum ContainerDigest {
+    /// `#[digestible(into = "T")]`
+    ///
+    /// Clones the container, converts it with `Into<T>` and digests the result
+    Into(Type),
+    /// `#[digestible(from_ref = "T")]`
+    ///
+    /// Creates a `T` with `From<&Self>` and digests the result
+    FromRef(Type),
+    /// `#[digestible(with = path)]`
+    ///
+    /// Calls `path::<B, W>(self, writer)`
+    With(Path),
+    /// `#[digestible(bytes)]`
+    ///
+    /// Writes the raw bytes of the container. The container must not contain padding
+    Bytes,
+}
 mod keywords {
     use syn::custom_keyword;
     custom_keyword!(type_header);
@@ -39,52 +55,115 @@ mod keywords {
     custom_keyword!(HashName);
     custom_keyword!(type_id);
     custom_keyword!(hash);
+    custom_keyword!(transparent);
+    custom_keyword!(into);
+    custom_keyword!(from_ref);
+    custom_keyword!(with);
+    custom_keyword!(bytes);
+    custom_keyword!(endian);
+    custom_keyword!(schema);
 }
+/// Attributes that replace the field by field digest
+const DIGEST_AS_KEYS: &[&str] = &["into", "from_ref", "with", "bytes"];
 #[derive(Debug, Default)]
 pub struct ContainerAttrs {
     pub type_header: TypeHeader,
     pub impl_hash: Option<Path>,
+    pub transparent: bool,
+    pub digest_as: Option<ContainerDigest>,
+    /// `#[digestible(crate = "path")]`
+    pub crate_path: Option<Path>,
+    /// `#[digestible(endian = BigEndian)]`
+    pub endian: Option<Path>,
+    /// `#[digestible(schema)]` implements `DigestSchema`
+    pub schema: bool,
+    /// Every key that was set. Used for errors
+    pub keys: AttrKeys,
 }
-impl Parse for ContainerAttrs {
-    fn parse(input: ParseStream) -> syn::Result<Self> {
-        let mut type_header = TypeHeader::default();
-        let mut impl_hash = None;
+impl ContainerAttrs {
+    /// Parses the content of one `#[digestible(...)]` attribute into self
+    fn parse_attr(&mut self, input: ParseStream) -> syn::Result<()> {
         while !input.is_empty() {
-            if input.peek(syn::Token![,]) {
-                let _: syn::Token![,] = input.parse()?;
-            }
             let lookahead = input.lookahead1();
             if lookahead.peek(keywords::type_header) {
-                let _ = input.parse::<keywords::type_header>()?;
+                let key = input.parse::<keywords::type_header>()?;
+                self.keys.insert("type_header", key.span)?;
                 let _: syn::Token![=] = input.parse()?;
-                type_header = input.parse()?;
+                self.type_header = input.parse()?;
             } else if lookahead.peek(keywords::hash) {
-                let _ = input.parse::<keywords::hash>()?;
+                let key = input.parse::<keywords::hash>()?;
+                self.keys.insert("hash", key.span)?;
                 if input.peek(syn::Token![=]) {
                     let _: syn::Token![=] = input.parse()?;
-                    impl_hash = Some(byte_order_impl_path(input.parse()?));
+                    self.impl_hash = Some(byte_order_impl_path(input.parse()?));
                 } else {
-                    impl_hash = Some(byte_order_impl_path(parse_quote!(NativeEndian)));
+                    self.impl_hash = Some(byte_order_impl_path(parse_quote!(NativeEndian)));
                 }
+            } else if lookahead.peek(keywords::transparent) {
+                let key = input.parse::<keywords::transparent>()?;
+                self.keys.insert("transparent", key.span)?;
+                self.transparent = true;
+            } else if lookahead.peek(keywords::into) {
+                let key = input.parse::<keywords::into>()?;
+                self.keys.insert("into", key.span)?;
+                let _: syn::Token![=] = input.parse()?;
+                self.digest_as = Some(ContainerDigest::Into(parse_maybe_quoted(input)?));
+            } else if lookahead.peek(keywords::from_ref) {
+                let key = input.parse::<keywords::from_ref>()?;
+                self.keys.insert("from_ref", key.span)?;
+                let _: syn::Token![=] = input.parse()?;
+                self.digest_as = Some(ContainerDigest::FromRef(parse_maybe_quoted(input)?));
+            } else if lookahead.peek(keywords::with) {
+                let key = input.parse::<keywords::with>()?;
+                self.keys.insert("with", key.span)?;
+                let _: syn::Token![=] = input.parse()?;
+                self.digest_as = Some(ContainerDigest::With(parse_maybe_quoted(input)?));
+            } else if lookahead.peek(keywords::bytes) {
+                let key = input.parse::<keywords::bytes>()?;
+                self.keys.insert("bytes", key.span)?;
+                self.digest_as = Some(ContainerDigest::Bytes);
+            } else if lookahead.peek(keywords::endian) {
+                let key = input.parse::<keywords::endian>()?;
+                self.keys.insert("endian", key.span)?;
+                let _: syn::Token![=] = input.parse()?;
+                self.endian = Some(byte_order_impl_path(input.parse()?));
+            } else if lookahead.peek(keywords::schema) {
+                let key = input.parse::<keywords::schema>()?;
+                self.keys.insert("schema", key.span)?;
+                self.schema = true;
+            } else if lookahead.peek(syn::Token![crate]) {
+                let key: syn::Token![crate] = input.parse()?;
+                self.keys.insert("crate", key.span)?;
+                let _: syn::Token![=] = input.parse()?;
+                self.crate_path = Some(parse_maybe_quoted(input)?);
             } else {
                 return Err(lookahead.error());
             }
+            if !input.is_empty() {
+                let _: syn::Token![,] = input.parse()?;
+            }
+        }
+        Ok(())
+    }
+    /// Checks for attributes that can not be used together
+    fn validate(&self) -> syn::Result<()> {
+        self.keys.conflicts("transparent", DIGEST_AS_KEYS)?;
+        self.keys.conflicts("transparent", &["type_header"])?;
+        for (index, key) in DIGEST_AS_KEYS.iter().enumerate() {
+            self.keys.conflicts(key, &DIGEST_AS_KEYS[index + 1..])?;
+            self.keys.conflicts(key, &["type_header"])?;
         }
-        let attr = Self {
-            type_header,
-            impl_hash,
-        };
-        Ok(attr)
+        self.keys.conflicts("bytes", &["endian"])?;
+        Ok(())
     }
 }
 pub fn get_container_attrs(attrs: &[Attribute]) -> syn::Result<ContainerAttrs> {
-    let attrs = attrs
-        .iter()
-        .find(|v| v.path().is_ident("digestible"))
-        .map(|v| v.parse_args::<ContainerAttrs>())
-        .transpose()?
-        .unwrap_or_default();
-    Ok(attrs)
+    let mut container_attrs = ContainerAttrs::default();
+    for attr in attrs.iter().filter(|v| v.path().is_ident("digestible")) {
+        attr.parse_args_with(|input: ParseStream| container_attrs.parse_attr(input))?;
+    }
+    container_attrs.validate()?;
+    Ok(container_attrs)
 }
 
-use crate::utils::byte_order_impl_path;
+use crate::utils::{byte_order_impl_path, parse_maybe_quoted, AttrKeys};
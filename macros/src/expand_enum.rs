@@ -1,12 +1,52 @@
-use crate::container_attrs::{get_container_attrs, TypeHeader};
+use crate::container_attrs::{ContainerAttrs, TypeHeader};
 use crate::fields::Field;
+use crate::schema::Schema;
 use crate::utils;
-use crate::utils::{digest_writer, digestible_path, private_path};
+use crate::utils::{digestible_path, private_path, AttrKeys};
 use proc_macro2::{Ident, TokenStream};
 use quote::{format_ident, quote, ToTokens};
+use syn::parse::ParseStream;
 use syn::Result;
-use syn::{DeriveInput, Path};
+use syn::{parse_quote, Attribute, DeriveInput, Path};
 
+mod keywords {
+    use syn::custom_keyword;
+    custom_keyword!(skip);
+}
+/// `#[digestible(...)]` on an enum variant
+#[derive(Debug, Default)]
+pub struct VariantAttr {
+    /// Only the name of the variant is written
+    pub skip: bool,
+    /// Every key that was set. Used for errors
+    pub keys: AttrKeys,
+}
+impl VariantAttr {
+    /// Parses every `#[digestible(...)]` attribute on a variant
+    pub fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
+        let mut variant_attr = Self::default();
+        for attr in attrs.iter().filter(|v| v.path().is_ident("digestible")) {
+            attr.parse_args_with(|input: ParseStream| variant_attr.parse_attr(input))?;
+        }
+        Ok(variant_attr)
+    }
+    fn parse_attr(&mut self, input: ParseStream) -> Result<()> {
+        while !input.is_empty() {
+            let lookahead = input.lookahead1();
+            if lookahead.peek(keywords::skip) {
+                let key = input.parse::<keywords::skip>()?;
+                self.keys.insert("skip", key.span)?;
+                self.skip = true;
+            } else {
+                return Err(lookahead.error());
+            }
+            if !input.is_empty() {
+                let _: syn::Token![,] = input.parse()?;
+            }
+        }
+        Ok(())
+    }
+}
 pub enum EnumType {
     Unit,
     Tuple,
@@ -15,14 +55,13 @@ pub enum EnumType {
 pub struct Variant<'a> {
     pub ident: syn::Ident,
     pub fields: Vec<Field<'a>>,
-    #[allow(dead_code)]
-    pub endian: &'a Ident,
-    pub writer: &'a Ident,
+    pub attr: VariantAttr,
     pub enum_type: EnumType,
 }
 
 impl<'a> Variant<'a> {
-    pub fn new(variant: syn::Variant, endian: &'a Ident, writer: &'a Ident) -> syn::Result<Self> {
+    pub fn new(variant: syn::Variant, endian: &'a Path, writer: &'a Ident) -> syn::Result<Self> {
+        let attr = VariantAttr::from_attrs(&variant.attrs)?;
         let mut fields: Vec<Field<'a>> = Vec::with_capacity(variant.fields.len());
         for (index, field) in variant.fields.iter().enumerate() {
             let field = Field::new(field.clone(), index, endian, writer)?;
@@ -36,71 +75,52 @@ impl<'a> Variant<'a> {
         Ok(Self {
             ident: variant.ident,
             fields,
-            endian,
-            writer,
+            attr,
             enum_type,
         })
     }
-    pub fn catch_block(&self, enum_name: &Ident) -> TokenStream {
+    /// The match arm for this variant.
+    ///
+    /// Skipped fields are not bound. So the fields are digested exactly like a struct field
+    pub fn match_arm(&self, enum_name: &Ident, endian: &Path) -> TokenStream {
         let ident = &self.ident;
-        let writer = self.writer;
-        let fields: Vec<_> = self.fields.iter().map(|v| &v.ident).collect();
-        let fn_name = format_ident!("digest_{}", self.ident);
-        match self.enum_type {
-            EnumType::Unit => {
-                quote! {
-                    #enum_name::#ident => {
-                        #fn_name(#writer);
-                    }
-                }
-            }
+        let write_variant_name: Path = private_path!(write_variant_name);
+        let fields: Vec<&Field> = if self.attr.skip {
+            Vec::new()
+        } else {
+            self.fields.iter().filter(|v| !v.attr.skip).collect()
+        };
+        let pattern = match self.enum_type {
+            EnumType::Unit => quote! {},
+            EnumType::Tuple if self.attr.skip => quote! {(..)},
             EnumType::Tuple => {
-                quote! {
-                    #enum_name::#ident(#(#fields),*) => {
-                        #fn_name(#writer, #(#fields),*);
+                let bindings = self.fields.iter().map(|v| {
+                    if v.attr.skip {
+                        quote! {_}
+                    } else {
+                        v.ident.to_token_stream()
                     }
-                }
+                });
+                quote! {(#(#bindings),*)}
             }
             EnumType::Struct => {
-                quote! {
-                    #enum_name::#ident{#(#fields),*} => {
-                        #fn_name(#writer, #(#fields),*);
-                    }
-                }
+                let bindings = fields.iter().map(|v| &v.ident);
+                quote! {{#(#bindings,)* ..}}
             }
-        }
-    }
-}
-impl ToTokens for Variant<'_> {
-    fn to_tokens(&self, tokens: &mut TokenStream) {
-        let fn_name = format_ident!("digest_{}", self.ident);
-        let fields_def: Vec<TokenStream> = self
-            .fields
-            .iter()
-            .map(|v| {
-                let ty = &v.ty;
-                let ident = &v.ident;
-                quote! {
-                    #ident: &#ty
-                }
-            })
-            .collect();
-        let fields = &self.fields;
-        let digest_writer = digest_writer();
-        let ident = &self.ident;
-        let writer = self.writer;
-        let result = quote! {
-            let #fn_name = |#writer: &mut W, #(#fields_def),*| {
-                #digest_writer::write(writer, stringify!(#ident).as_bytes());
-                #(#fields)*
-            };
         };
-        tokens.extend(result);
+        quote! {
+            #enum_name::#ident #pattern => {
+                #write_variant_name::<#endian, _>(writer, stringify!(#ident));
+                #(#fields)*
+            }
+        }
     }
 }
-pub(crate) fn expand(derive_input: DeriveInput) -> Result<TokenStream> {
+pub(crate) fn expand(
+    derive_input: DeriveInput,
+    container_attrs: ContainerAttrs,
+) -> Result<TokenStream> {
     let DeriveInput {
-        attrs,
         ident,
         mut generics,
         data,
@@ -114,33 +134,54 @@ pub(crate) fn expand(derive_input: DeriveInput) -> Result<TokenStream> {
     };
     utils::add_digestible_trait(&mut generics);
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
-    let container_attrs = get_container_attrs(&attrs)?;
+    if let Some(span) = container_attrs.keys.get("transparent") {
+        return Err(syn::Error::new(
+            span,
+            "transparent can only be used on structs",
+        ));
+    }
 
     let writer = format_ident!("writer");
     let order = format_ident!("B");
-    let digest_writer = digest_writer();
+    let digest_order: Path = container_attrs
+        .endian
+        .clone()
+        .unwrap_or_else(|| parse_quote!(#order));
     let header_write = match container_attrs.type_header {
         TypeHeader::None => quote! {},
         TypeHeader::HashName => {
-            let type_name: Path = private_path!(type_name);
-
+            let write_enum_header: Path = private_path!(write_enum_header);
             quote! {
-                #digest_writer::write(writer, #type_name::<Self>().as_bytes());
-                #digest_writer::write(writer, b"::");
+                #write_enum_header::<Self, #digest_order, _>(writer);
             }
         }
-        TypeHeader::TypeId { .. } => {
-            todo!("type_id")
-        }
     };
     let mut variants = Vec::with_capacity(as_enum.variants.len());
     for variant in as_enum.variants {
-        let variant = Variant::new(variant, &order, &writer)?;
+        let variant = Variant::new(variant, &digest_order, &writer)?;
         variants.push(variant);
     }
-    let catch_block: Vec<_> = variants.iter().map(|v| v.catch_block(&ident)).collect();
+    let match_arms: Vec<_> = variants
+        .iter()
+        .map(|v| v.match_arm(&ident, &digest_order))
+        .collect();
     let digestible = digestible_path();
     let byte_order_path = crate::utils::byte_order_path();
+    let impl_schema = if container_attrs.schema {
+        let mut schema = Schema::new("enum", &ident);
+        schema.container(&container_attrs);
+        schema.len(variants.len());
+        for variant in &variants {
+            schema.str(&variant.ident.to_string());
+            schema.bool(variant.attr.skip);
+            if !variant.attr.skip {
+                schema.fields(&variant.fields);
+            }
+        }
+        schema.expand(&ident, &generics)
+    } else {
+        quote! {}
+    };
     let impl_hash = if let Some(impl_hash) = container_attrs.impl_hash {
         utils::impl_hash(
             &ident,
@@ -153,26 +194,24 @@ pub(crate) fn expand(derive_input: DeriveInput) -> Result<TokenStream> {
         quote! {}
     };
     let result = quote! {
-        const _: () = {
-            #[allow(unused_extern_crates, clippy::useless_attribute)]
-            extern crate digestible as _digestible;
-            #[automatically_derived]
-            impl #impl_generics #digestible for #ident #ty_generics #where_clause {
-                #[allow(non_snake_case)]
-                fn digest<#order: #byte_order_path, W: _digestible::DigestWriter>(
-                    &self,
-                    writer: &mut W,
-                ) {
-                    #(#variants)*
-                    #header_write
-                    match self {
-                        #(#catch_block)*
-                    }
+        #[automatically_derived]
+        impl #impl_generics #digestible for #ident #ty_generics #where_clause {
+            fn digest<#order: #byte_order_path, W: _digestible::DigestWriter>(
+                &self,
+                writer: &mut W,
+            ) {
+                #header_write
+                match self {
+                    #(#match_arms)*
                 }
             }
-            #impl_hash
-        };
+        }
+        #impl_hash
+        #impl_schema
     };
 
-    Ok(result)
+    Ok(utils::wrap_in_const(
+        container_attrs.crate_path.as_ref(),
+        result,
+    ))
 }
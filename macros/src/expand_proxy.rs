@@ -0,0 +1,197 @@
+use crate::container_attrs::{ContainerAttrs, ContainerDigest};
+use crate::schema::Schema;
+use crate::utils;
+use crate::utils::{digestible_path, private_path};
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{parse_quote, Attribute, Data, DeriveInput, Path, Result, Type};
+
+/// Expands a container that is not digested field by field.
+///
+/// Nothing about the container itself is written. Including the type header.
+pub(crate) fn expand(
+    derive_input: DeriveInput,
+    container_attrs: ContainerAttrs,
+    digest_as: ContainerDigest,
+) -> Result<TokenStream> {
+    let DeriveInput {
+        attrs,
+        ident,
+        mut generics,
+        data,
+        ..
+    } = derive_input;
+    if matches!(data, Data::Union(_))
+        && !matches!(digest_as, ContainerDigest::With(_) | ContainerDigest::Bytes)
+    {
+        return Err(syn::Error::new_spanned(
+            &ident,
+            "unions can only be digested with #[digestible(with = ...)] or #[digestible(bytes)]",
+        ));
+    }
+    utils::add_digestible_trait(&mut generics);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let order = format_ident!("B");
+    let digest_order: Path = container_attrs
+        .endian
+        .clone()
+        .unwrap_or_else(|| parse_quote!(#order));
+    let digestible = digestible_path();
+    let mut extra_items = quote! {};
+    let convert = match &digest_as {
+        ContainerDigest::Into(ty) => {
+            let into: Path = private_path!(Into);
+            let clone: Path = private_path!(Clone);
+            quote! {
+                let proxy = <Self as #into<#ty>>::into(<Self as #clone>::clone(self));
+                <#ty as #digestible>::digest::<#digest_order, W>(&proxy, writer);
+            }
+        }
+        ContainerDigest::FromRef(ty) => {
+            let from: Path = private_path!(From);
+            quote! {
+                let proxy = <#ty as #from<&Self>>::from(self);
+                <#ty as #digestible>::digest::<#digest_order, W>(&proxy, writer);
+            }
+        }
+        ContainerDigest::With(path) => {
+            quote! {
+                #path::<#digest_order, W>(self, writer);
+            }
+        }
+        ContainerDigest::Bytes => {
+            let bytes_span = container_attrs
+                .keys
+                .get("bytes")
+                .unwrap_or_else(|| ident.span());
+            if !generics.params.is_empty() {
+                return Err(syn::Error::new(
+                    bytes_span,
+                    "bytes can not be used on generic types",
+                ));
+            }
+            extra_items = no_padding_checks(&ident, bytes_span, &attrs, &data)?;
+            let digest_raw_bytes = utils::digest_raw_bytes_path();
+            quote! {
+                #digest_raw_bytes::<#digest_order, W>(self, writer);
+            }
+        }
+    };
+    let byte_order_path = utils::byte_order_path();
+    let impl_schema = if container_attrs.schema {
+        let (kind, fields): (&str, Vec<&Type>) = match &data {
+            Data::Struct(data) => ("struct", data.fields.iter().map(|v| &v.ty).collect()),
+            Data::Enum(_) => ("enum", Vec::new()),
+            Data::Union(data) => ("union", data.fields.named.iter().map(|v| &v.ty).collect()),
+        };
+        let mut schema = Schema::new(kind, &ident);
+        schema.endian(container_attrs.endian.as_ref());
+        schema.digest_as(&digest_as, &fields);
+        schema.expand(&ident, &generics)
+    } else {
+        quote! {}
+    };
+    let impl_hash = if let Some(impl_hash) = container_attrs.impl_hash {
+        utils::impl_hash(
+            &ident,
+            impl_hash,
+            &impl_generics,
+            &ty_generics,
+            &where_clause,
+        )
+    } else {
+        quote! {}
+    };
+
+    let result = quote! {
+        #extra_items
+        #[automatically_derived]
+        impl #impl_generics #digestible for #ident #ty_generics #where_clause {
+            fn digest<#order: #byte_order_path, W: _digestible::DigestWriter>(
+                &self,
+                writer: &mut W,
+            ) {
+                #convert
+            }
+        }
+        #impl_hash
+        #impl_schema
+    };
+    Ok(utils::wrap_in_const(
+        container_attrs.crate_path.as_ref(),
+        result,
+    ))
+}
+/// Checks that the container can be digested as its raw bytes and implements `NoPadding` for it.
+///
+/// - Structs must be `repr(C)`, `repr(transparent)` or `repr(packed)` and the size of the struct must equal the size of its fields.
+/// - Every field of a union must have the same size as the union.
+/// - Every field must implement `NoPadding`.
+fn no_padding_checks(
+    ident: &Ident,
+    bytes_span: Span,
+    attrs: &[Attribute],
+    data: &Data,
+) -> Result<TokenStream> {
+    let fields: Vec<&Type> = match data {
+        Data::Struct(data) => data.fields.iter().map(|v| &v.ty).collect(),
+        Data::Union(data) => data.fields.named.iter().map(|v| &v.ty).collect(),
+        Data::Enum(_) => {
+            return Err(syn::Error::new(
+                bytes_span,
+                "bytes can only be used on structs and unions",
+            ))
+        }
+    };
+    let mut has_repr = false;
+    for attr in attrs.iter().filter(|v| v.path().is_ident("repr")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("C")
+                || meta.path.is_ident("transparent")
+                || meta.path.is_ident("packed")
+            {
+                has_repr = true;
+            }
+            if meta.input.peek(syn::token::Paren) {
+                let _content;
+                syn::parenthesized!(_content in meta.input);
+            }
+            Ok(())
+        })?;
+    }
+    if !has_repr {
+        return Err(syn::Error::new(
+            bytes_span,
+            "bytes requires #[repr(C)], #[repr(transparent)] or #[repr(packed)]",
+        ));
+    }
+    let no_padding = utils::no_padding_path();
+    let size_of: Path = private_path!(size_of);
+    let size_check = match data {
+        Data::Union(_) => quote! {
+            #(
+                assert!(
+                    #size_of::<#ident>() == #size_of::<#fields>(),
+                    concat!("Every field of ", stringify!(#ident), " must be the same size as the union")
+                );
+            )*
+        },
+        _ => quote! {
+            assert!(
+                #size_of::<#ident>() == 0 #(+ #size_of::<#fields>())*,
+                concat!(stringify!(#ident), " contains padding")
+            );
+        },
+    };
+    Ok(quote! {
+        const _: () = {
+            #size_check
+        };
+        const _: fn() = || {
+            fn assert_no_padding<T: #no_padding>() {}
+            #(assert_no_padding::<#fields>();)*
+        };
+        unsafe impl #no_padding for #ident {}
+    })
+}
@@ -1,15 +1,18 @@
-use crate::container_attrs::{get_container_attrs, TypeHeader};
+use crate::container_attrs::{ContainerAttrs, TypeHeader};
 use crate::fields::Field;
+use crate::schema::Schema;
 use crate::utils;
-use crate::utils::{digest_writer, digestible_path, private_path};
+use crate::utils::{digestible_path, private_path};
 use proc_macro2::TokenStream;
-use quote::{format_ident, quote};
-use syn::{DeriveInput, Path};
+use quote::{format_ident, quote, ToTokens};
+use syn::{parse_quote, DeriveInput, Path};
 use syn::{Fields, Result};
 
-pub(crate) fn expand(derive_input: DeriveInput) -> Result<TokenStream> {
+pub(crate) fn expand(
+    derive_input: DeriveInput,
+    container_attrs: ContainerAttrs,
+) -> Result<TokenStream> {
     let DeriveInput {
-        attrs,
         ident,
         mut generics,
         data,
@@ -18,7 +21,6 @@ pub(crate) fn expand(derive_input: DeriveInput) -> Result<TokenStream> {
     utils::add_digestible_trait(&mut generics);
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-    let container_attrs = get_container_attrs(&attrs)?;
     let syn::Data::Struct(as_struct) = data else {
         // This is checked before
         unsafe {
@@ -29,19 +31,38 @@ pub(crate) fn expand(derive_input: DeriveInput) -> Result<TokenStream> {
     let mut fields = Vec::with_capacity(as_struct.fields.len());
     let writer = format_ident!("writer");
     let order = format_ident!("B");
+    let digest_order: Path = container_attrs
+        .endian
+        .clone()
+        .unwrap_or_else(|| parse_quote!(#order));
     for (index, field) in as_struct.fields.iter().enumerate() {
-        let field = Field::new(field.clone(), index, &order, &writer)?;
+        let field = Field::new(field.clone(), index, &digest_order, &writer)?;
         fields.push(field);
     }
-    let field_names: Vec<_> = fields.iter().map(|v| &v.ident).collect();
-
+    if let Some(span) = container_attrs.keys.get("transparent") {
+        if fields.iter().filter(|v| !v.attr.skip).count() != 1 {
+            return Err(syn::Error::new(
+                span,
+                "transparent structs must have exactly one field that is not skipped",
+            ));
+        }
+    }
+    // Skipped fields are not bound
     let expand_fields = match &as_struct.fields {
         Fields::Named(_) => {
+            let field_names = fields.iter().filter(|v| !v.attr.skip).map(|v| &v.ident);
             quote! {
-                let Self { #(#field_names),* } = self;
+                let Self { #(#field_names,)* .. } = self;
             }
         }
         Fields::Unnamed(_) => {
+            let field_names = fields.iter().map(|v| {
+                if v.attr.skip {
+                    quote! {_}
+                } else {
+                    v.ident.to_token_stream()
+                }
+            });
             quote! {
                 let Self(#(#field_names),*) = self;
             }
@@ -51,21 +72,25 @@ pub(crate) fn expand(derive_input: DeriveInput) -> Result<TokenStream> {
         }
     };
 
-    let digest_writer = digest_writer();
-
     let header_write = match container_attrs.type_header {
+        _ if container_attrs.transparent => quote! {},
         TypeHeader::None => quote! {},
         TypeHeader::HashName => {
-            let type_name: Path = private_path!(type_name);
+            let write_type_header: Path = private_path!(write_type_header);
             quote! {
-                #digest_writer::write(writer, #type_name::<Self>().as_bytes());
+                #write_type_header::<Self, #digest_order, _>(writer);
             }
         }
-        TypeHeader::TypeId { .. } => {
-            todo!("type_id")
-        }
     };
     let byte_order_path = utils::byte_order_path();
+    let impl_schema = if container_attrs.schema {
+        let mut schema = Schema::new("struct", &ident);
+        schema.container(&container_attrs);
+        schema.fields(&fields);
+        schema.expand(&ident, &generics)
+    } else {
+        quote! {}
+    };
     let impl_hash = if let Some(impl_hash) = container_attrs.impl_hash {
         utils::impl_hash(
             &ident,
@@ -80,23 +105,23 @@ pub(crate) fn expand(derive_input: DeriveInput) -> Result<TokenStream> {
 
     let digestible = digestible_path();
     let result = quote! {
-        const _: () = {
-            #[allow(unused_extern_crates, clippy::useless_attribute)]
-            extern crate digestible as _digestible;
-            #[automatically_derived]
-            impl  #impl_generics  #digestible for #ident #ty_generics #where_clause {
-                fn digest<#order: #byte_order_path, W: _digestible::DigestWriter>(
-                    &self,
-                    writer: &mut W,
-                ) {
-                    #header_write
-                    #expand_fields
-                    #(#fields)*
-                }
+        #[automatically_derived]
+        impl  #impl_generics  #digestible for #ident #ty_generics #where_clause {
+            fn digest<#order: #byte_order_path, W: _digestible::DigestWriter>(
+                &self,
+                writer: &mut W,
+            ) {
+                #header_write
+                #expand_fields
+                #(#fields)*
             }
-            #impl_hash
-        };
+        }
+        #impl_hash
+        #impl_schema
     };
 
-    Ok(result)
+    Ok(utils::wrap_in_const(
+        container_attrs.crate_path.as_ref(),
+        result,
+    ))
 }
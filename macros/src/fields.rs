@@ -1,8 +1,12 @@
-use crate::utils::{digest_with_path, digestible_path};
+use crate::utils::{
+    byte_order_impl_path, digest_with_path, digest_writer_path, digestible_path, private_path,
+    AttrKeys,
+};
 use proc_macro2::{Ident, TokenStream};
 use quote::{format_ident, quote, ToTokens};
-use syn::parse::{Parse, ParseStream};
-use syn::{parse_quote, Expr, Path, Type};
+use syn::ext::IdentExt;
+use syn::parse::ParseStream;
+use syn::{parse_quote, Attribute, Expr, LitStr, Path, Type};
 
 mod keywords {
     use syn::custom_keyword;
@@ -10,48 +14,112 @@ mod keywords {
     custom_keyword!(with);
     custom_keyword!(digest_with);
     custom_keyword!(as_ref);
+    custom_keyword!(endian);
+    custom_keyword!(redact);
+    custom_keyword!(salted);
+    custom_keyword!(keyed);
 }
 #[derive(Debug, Default)]
 pub struct FieldAttr {
     pub skip: bool,
     pub as_ref: Option<Type>,
     pub digest_with: Option<Path>,
+    /// Overrides the byte order for this field
+    pub endian: Option<Path>,
+    /// Only a presence marker is written
+    pub redact: bool,
+    /// The HMAC is written with the field name as the context
+    pub salted: bool,
+    /// The HMAC is written with the given context
+    pub keyed: Option<String>,
+    /// Every key that was set. Used for errors
+    pub keys: AttrKeys,
 }
-impl Parse for FieldAttr {
-    fn parse(input: ParseStream) -> syn::Result<Self> {
-        let mut skip = false;
-        let mut as_ref = None;
-        let mut digest_with: Option<Path> = None;
+impl FieldAttr {
+    /// Parses every `#[digestible(...)]` attribute on a field
+    pub fn from_attrs(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut field_attr = Self::default();
+        for attr in attrs.iter().filter(|v| v.path().is_ident("digestible")) {
+            attr.parse_args_with(|input: ParseStream| field_attr.parse_attr(input))?;
+        }
+        field_attr.validate()?;
+        Ok(field_attr)
+    }
+    /// Parses the content of one `#[digestible(...)]` attribute into self
+    fn parse_attr(&mut self, input: ParseStream) -> syn::Result<()> {
         while !input.is_empty() {
             let lookahead = input.lookahead1();
             if lookahead.peek(keywords::skip) {
-                let _ = input.parse::<keywords::skip>()?;
-                skip = true;
+                let key = input.parse::<keywords::skip>()?;
+                self.keys.insert("skip", key.span)?;
+                self.skip = true;
             } else if lookahead.peek(keywords::with) {
-                let _ = input.parse::<keywords::with>()?;
+                let key = input.parse::<keywords::with>()?;
+                self.keys.insert("with", key.span)?;
                 let _: syn::Token![=] = input.parse()?;
-                digest_with = Some(input.parse()?);
+                self.digest_with = Some(input.parse()?);
             } else if lookahead.peek(keywords::digest_with) {
-                let _ = input.parse::<keywords::digest_with>()?;
+                let key = input.parse::<keywords::digest_with>()?;
+                self.keys.insert("digest_with", key.span)?;
                 let _: syn::Token![=] = input.parse()?;
 
                 let internal_digest_with_path = digest_with_path(input.parse()?);
-                digest_with = Some(internal_digest_with_path);
+                self.digest_with = Some(internal_digest_with_path);
             } else if lookahead.peek(keywords::as_ref) {
-                let _ = input.parse::<keywords::as_ref>()?;
+                let key = input.parse::<keywords::as_ref>()?;
+                self.keys.insert("as_ref", key.span)?;
+                let _: syn::Token![=] = input.parse()?;
+                self.as_ref = Some(input.parse()?);
+            } else if lookahead.peek(keywords::endian) {
+                let key = input.parse::<keywords::endian>()?;
+                self.keys.insert("endian", key.span)?;
                 let _: syn::Token![=] = input.parse()?;
-                as_ref = Some(input.parse()?);
+                self.endian = Some(byte_order_impl_path(input.parse()?));
+            } else if lookahead.peek(keywords::redact) {
+                let key = input.parse::<keywords::redact>()?;
+                self.keys.insert("redact", key.span)?;
+                self.redact = true;
+            } else if lookahead.peek(keywords::salted) {
+                let key = input.parse::<keywords::salted>()?;
+                self.keys.insert("salted", key.span)?;
+                self.salted = true;
+            } else if lookahead.peek(keywords::keyed) {
+                let key = input.parse::<keywords::keyed>()?;
+                self.keys.insert("keyed", key.span)?;
+                let _: syn::Token![=] = input.parse()?;
+                let context: LitStr = input.parse()?;
+                self.keyed = Some(context.value());
             } else {
                 return Err(lookahead.error());
             }
+            if !input.is_empty() {
+                let _: syn::Token![,] = input.parse()?;
+            }
         }
-
-        let attr = Self {
-            skip,
-            as_ref,
-            digest_with,
-        };
-        Ok(attr)
+        Ok(())
+    }
+    /// Checks for attributes that can not be used together
+    fn validate(&self) -> syn::Result<()> {
+        self.keys.conflicts(
+            "skip",
+            &[
+                "with",
+                "digest_with",
+                "as_ref",
+                "endian",
+                "redact",
+                "salted",
+                "keyed",
+            ],
+        )?;
+        self.keys.conflicts(
+            "redact",
+            &["with", "digest_with", "as_ref", "endian", "salted", "keyed"],
+        )?;
+        self.keys.conflicts("salted", &["keyed"])?;
+        self.keys.conflicts("with", &["digest_with"])?;
+        self.keys.conflicts("as_ref", &["with", "digest_with"])?;
+        Ok(())
     }
 }
 
@@ -59,8 +127,11 @@ impl Parse for FieldAttr {
 pub struct Field<'a> {
     pub ty: syn::Type,
     pub ident: Ident,
+    /// The name passed to `enter_field`. The index for tuple fields
+    pub name: String,
     pub attr: FieldAttr,
-    pub endian: &'a Ident,
+    /// The byte order of the container
+    pub endian: &'a Path,
     pub writer: &'a Ident,
 }
 
@@ -68,18 +139,17 @@ impl<'a> Field<'a> {
     pub fn new(
         field: syn::Field,
         index: usize,
-        endian: &'a Ident,
+        endian: &'a Path,
         writer: &'a Ident,
     ) -> syn::Result<Self> {
-        let attr = field
-            .attrs
-            .iter()
-            .find(|v| v.path().is_ident("digestible"))
-            .map(|v| v.parse_args::<FieldAttr>())
-            .transpose()?
-            .unwrap_or_default();
+        let attr = FieldAttr::from_attrs(&field.attrs)?;
 
+        let name = match &field.ident {
+            Some(ident) => ident.unraw().to_string(),
+            None => index.to_string(),
+        };
         Ok(Self {
+            name,
             ident: field
                 .ident
                 .as_ref()
@@ -92,6 +162,28 @@ impl<'a> Field<'a> {
         })
     }
 }
+impl Field<'_> {
+    /// The context of the HMAC for `salted` and `keyed` fields
+    pub fn keyed_context(&self) -> Option<&str> {
+        if self.attr.salted {
+            Some(&self.name)
+        } else {
+            self.attr.keyed.as_deref()
+        }
+    }
+}
+/// If the type is written as `Option<...>`
+fn is_option(ty: &Type) -> bool {
+    let Type::Path(path) = ty else {
+        return false;
+    };
+    path.qself.is_none()
+        && path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Option" && !segment.arguments.is_empty())
+}
 impl ToTokens for Field<'_> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         if self.attr.skip {
@@ -104,21 +196,47 @@ impl ToTokens for Field<'_> {
         } else {
             &self.ty
         };
-        let endian = self.endian;
+        let endian = self.attr.endian.as_ref().unwrap_or(self.endian);
         let writer = self.writer;
         let variable_ref: Expr = if self.attr.as_ref.is_some() {
             parse_quote! {#ident.as_ref()}
         } else {
             parse_quote! {#ident}
         };
-        let result = if let Some(digest_with) = &self.attr.digest_with {
+        let mut digest = if let Some(digest_with) = &self.attr.digest_with {
             quote! {
                 #digest_with::<#endian,_>(#variable_ref, #writer);
             }
         } else {
             quote! {<#ty as #digestible>::digest::<#endian, _>(#variable_ref,#writer);}
         };
-
-        tokens.extend(result);
+        if self.attr.redact {
+            let write_presence: Path = private_path!(write_presence);
+            let present = if is_option(&self.ty) {
+                quote! {#ident.is_some()}
+            } else {
+                quote! {{
+                    let _ = #ident;
+                    true
+                }}
+            };
+            digest = quote! {
+                #write_presence(#writer, #present);
+            };
+        } else if let Some(context) = self.keyed_context() {
+            let digest_keyed: Path = private_path!(digest_keyed);
+            digest = quote! {
+                #digest_keyed(#writer, #context, |#writer| {
+                    #digest
+                });
+            };
+        }
+        let digest_writer = digest_writer_path();
+        let name = &self.name;
+        tokens.extend(quote! {
+            #digest_writer::enter_field(#writer, #name);
+            #digest
+            #digest_writer::exit(#writer);
+        });
     }
 }
@@ -1,26 +1,36 @@
 mod container_attrs;
 mod expand_enum;
+mod expand_proxy;
 mod expand_struct;
 mod fields;
+mod schema;
 mod utils;
 
+use crate::container_attrs::get_container_attrs;
 use proc_macro::TokenStream;
 use syn::{parse_macro_input, DeriveInput};
 
 #[proc_macro_derive(Digestible, attributes(digestible))]
 pub fn digestible(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
-    // Check if its an enum
-    let result = match &input.data {
-        syn::Data::Struct(_) => expand_struct::expand(input),
-        syn::Data::Enum(_) => expand_enum::expand(input),
-        _ => Err(syn::Error::new_spanned(
-            input,
-            "digestible can only be derived for structs and enums",
-        )),
-    };
-    match result {
+    match expand(input) {
         Ok(ok) => ok.into(),
         Err(err) => err.to_compile_error().into(),
     }
 }
+
+fn expand(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let mut container_attrs = get_container_attrs(&input.attrs)?;
+    if let Some(digest_as) = container_attrs.digest_as.take() {
+        return expand_proxy::expand(input, container_attrs, digest_as);
+    }
+    // Check if its an enum
+    match &input.data {
+        syn::Data::Struct(_) => expand_struct::expand(input, container_attrs),
+        syn::Data::Enum(_) => expand_enum::expand(input, container_attrs),
+        syn::Data::Union(_) => Err(syn::Error::new_spanned(
+            input.ident,
+            "unions can only be digested with #[digestible(with = ...)] or #[digestible(bytes)]",
+        )),
+    }
+}
@@ -0,0 +1,130 @@
+use crate::container_attrs::{ContainerAttrs, ContainerDigest, TypeHeader};
+use crate::fields::Field;
+use crate::utils::digest_schema_path;
+use proc_macro2::{Ident, TokenStream};
+use quote::{quote, ToTokens};
+use syn::{parse_quote, GenericParam, Generics, Path, Type};
+
+/// The calls on `SchemaHasher` that build the fingerprint.
+///
+/// Everything that changes how the type is digested must be added
+pub struct Schema(Vec<TokenStream>);
+impl Schema {
+    /// Starts the schema with the kind of container and its name
+    pub fn new(kind: &str, ident: &Ident) -> Self {
+        let mut schema = Self(Vec::new());
+        schema.str(kind);
+        schema.str(&ident.to_string());
+        schema
+    }
+    pub fn str(&mut self, value: &str) {
+        self.0.push(quote!(.str(#value)));
+    }
+    pub fn len(&mut self, len: usize) {
+        self.0.push(quote!(.len(#len)));
+    }
+    pub fn bool(&mut self, value: bool) {
+        self.0.push(quote!(.bool(#value)));
+    }
+    pub fn fingerprint(&mut self, ty: &Type) {
+        let digest_schema = digest_schema_path();
+        self.0
+            .push(quote!(.fingerprint(<#ty as #digest_schema>::SCHEMA_FINGERPRINT)));
+    }
+    pub fn path(&mut self, path: &Path) {
+        self.str(&path.to_token_stream().to_string());
+    }
+    pub fn endian(&mut self, endian: Option<&Path>) {
+        // Only the name. The path to byteorder does not change the digest
+        match endian.and_then(|v| v.segments.last()) {
+            Some(segment) => self.str(&segment.ident.to_string()),
+            None => self.str(""),
+        }
+    }
+    /// The attributes of the container that are used by field by field digests
+    pub fn container(&mut self, container_attrs: &ContainerAttrs) {
+        match container_attrs.type_header {
+            _ if container_attrs.transparent => self.str("transparent"),
+            TypeHeader::None => self.str("none"),
+            TypeHeader::HashName => self.str("hash_name"),
+        }
+        self.endian(container_attrs.endian.as_ref());
+    }
+    /// Replaces the field by field digest
+    pub fn digest_as(&mut self, digest_as: &ContainerDigest, fields: &[&Type]) {
+        match digest_as {
+            ContainerDigest::Into(ty) => {
+                self.str("into");
+                self.fingerprint(ty);
+            }
+            ContainerDigest::FromRef(ty) => {
+                self.str("from_ref");
+                self.fingerprint(ty);
+            }
+            ContainerDigest::With(path) => {
+                self.str("with");
+                self.path(path);
+            }
+            ContainerDigest::Bytes => {
+                // Field types only need to implement NoPadding
+                self.str("bytes");
+                self.len(fields.len());
+                for ty in fields {
+                    self.str(&ty.to_token_stream().to_string());
+                }
+            }
+        }
+    }
+    pub fn fields(&mut self, fields: &[Field]) {
+        self.len(fields.len());
+        for field in fields {
+            self.str(&field.ident.to_string());
+            let attr = &field.attr;
+            if attr.skip {
+                self.str("skip");
+                continue;
+            }
+            if attr.redact {
+                self.str("redact");
+                continue;
+            }
+            if let Some(context) = field.keyed_context() {
+                self.str("keyed");
+                self.str(context);
+            }
+            if let Some(digest_with) = &attr.digest_with {
+                self.str("with");
+                self.path(digest_with);
+            } else if let Some(as_ref) = &attr.as_ref {
+                self.str("as_ref");
+                self.fingerprint(as_ref);
+            } else {
+                self.str("value");
+                self.fingerprint(&field.ty);
+            }
+            self.endian(attr.endian.as_ref());
+        }
+    }
+    /// Implements `DigestSchema` for the container.
+    ///
+    /// Every type parameter must implement `DigestSchema`
+    pub fn expand(self, ident: &Ident, generics: &Generics) -> TokenStream {
+        let digest_schema = digest_schema_path();
+        let mut generics = generics.clone();
+        for param in &mut generics.params {
+            if let GenericParam::Type(ty) = param {
+                ty.bounds.push(parse_quote!(#digest_schema));
+            }
+        }
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        let calls = self.0;
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics #digest_schema for #ident #ty_generics #where_clause {
+                const SCHEMA_FINGERPRINT: [u8; 32] = _digestible::schema::SchemaHasher::new("derive")
+                    #(#calls)*
+                    .finish();
+            }
+        }
+    }
+}
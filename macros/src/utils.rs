@@ -1,15 +1,49 @@
-use proc_macro2::{Ident, TokenStream};
-use syn::{parse_quote, GenericParam, Generics, ImplGenerics, Path, TypeGenerics, WhereClause};
+use proc_macro2::{Ident, Span, TokenStream};
+use syn::parse::{Parse, ParseStream};
+use syn::{
+    parse_quote, GenericParam, Generics, ImplGenerics, LitStr, Path, TypeGenerics, WhereClause,
+};
 
+/// Wraps the generated code in a `const _: () = { ... };` block.
+///
+/// Inside the block the digestible crate is always available as `_digestible`.
+/// Every path built in this module starts with `_digestible`
+/// so `#[digestible(crate = "...")]` only has to change the import.
+pub fn wrap_in_const(crate_path: Option<&Path>, code: TokenStream) -> TokenStream {
+    let import = match crate_path {
+        Some(path) => quote! {
+            use #path as _digestible;
+        },
+        None => quote! {
+            #[allow(unused_extern_crates, clippy::useless_attribute)]
+            extern crate digestible as _digestible;
+        },
+    };
+    quote! {
+        const _: () = {
+            #import
+            #code
+        };
+    }
+}
 pub fn digestible_path() -> Path {
     parse_quote!(_digestible::Digestible)
 }
-pub fn digest_writer() -> Path {
+pub fn digest_writer_path() -> Path {
     parse_quote!(_digestible::DigestWriter)
 }
+pub fn digest_schema_path() -> Path {
+    parse_quote!(_digestible::schema::DigestSchema)
+}
 pub fn digest_with_path(path: Path) -> Path {
     parse_quote!(_digestible::digest_with::#path)
 }
+pub fn digest_raw_bytes_path() -> Path {
+    parse_quote!(_digestible::digest_with::raw_bytes::digest_raw_bytes)
+}
+pub fn no_padding_path() -> Path {
+    parse_quote!(_digestible::digest_with::raw_bytes::NoPadding)
+}
 pub fn digester_using_hasher() -> Path {
     parse_quote!(_digestible::hash_digester::DigesterUsingHasher)
 }
@@ -21,6 +55,54 @@ pub fn byte_order_impl_path(ident: Ident) -> Path {
     parse_quote!(_digestible::byteorder::#ident)
 }
 
+/// Parses either `T` or `"T"`. Matching how serde accepts paths and types as strings.
+pub fn parse_maybe_quoted<T: Parse>(input: ParseStream) -> syn::Result<T> {
+    if input.peek(LitStr) {
+        let lit: LitStr = input.parse()?;
+        lit.parse()
+    } else {
+        input.parse()
+    }
+}
+
+/// The keys set in `#[digestible(...)]` attributes and where they were set.
+#[derive(Debug, Default)]
+pub struct AttrKeys(Vec<(&'static str, Span)>);
+impl AttrKeys {
+    /// Errors if the key was already set
+    pub fn insert(&mut self, key: &'static str, span: Span) -> syn::Result<()> {
+        if self.get(key).is_some() {
+            return Err(syn::Error::new(
+                span,
+                format!("duplicate digestible attribute `{key}`"),
+            ));
+        }
+        self.0.push((key, span));
+        Ok(())
+    }
+    pub fn get(&self, key: &str) -> Option<Span> {
+        self.0
+            .iter()
+            .find(|(set_key, _)| *set_key == key)
+            .map(|(_, span)| *span)
+    }
+    /// Errors if `key` and any of `others` are both set
+    pub fn conflicts(&self, key: &str, others: &[&str]) -> syn::Result<()> {
+        if self.get(key).is_none() {
+            return Ok(());
+        }
+        for other in others {
+            if let Some(span) = self.get(other) {
+                return Err(syn::Error::new(
+                    span,
+                    format!("`{other}` can not be combined with `{key}`"),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
 macro_rules! private_path {
     // `()` indicates that the macro takes no argument.
     ($key:ident) => {
@@ -0,0 +1,71 @@
+//! The digestible crate is only available as `framework`.
+//! So the generated code must use the path given with `crate`
+use digestible_macros::Digestible;
+use framework::byteorder::{ByteOrder, LittleEndian};
+use framework::{DigestWriter, Digestible as _};
+
+mod reexport {
+    pub use framework as digestible_reexport;
+}
+
+fn digest_with<B: ByteOrder, W: DigestWriter>(value: &With, writer: &mut W) {
+    writer.write_u32::<B>(value.0);
+}
+
+#[derive(Digestible)]
+#[digestible(crate = "framework", hash, schema)]
+pub struct Struct {
+    pub id: u32,
+    #[digestible(endian = BigEndian)]
+    pub port: u16,
+    #[digestible(digest_with = digest_with_hash)]
+    pub hashed: String,
+    #[digestible(salted)]
+    pub email: String,
+    #[digestible(redact)]
+    pub phone: Option<String>,
+}
+
+#[derive(Digestible)]
+#[digestible(crate = "crate::reexport::digestible_reexport", type_header = HashName)]
+pub enum Enum<T> {
+    A,
+    B(T),
+    #[digestible(skip)]
+    C {
+        value: u32,
+    },
+}
+
+#[derive(Digestible)]
+#[digestible(crate = "framework", transparent)]
+pub struct Transparent(u32);
+
+#[derive(Digestible)]
+#[digestible(crate = "framework", with = digest_with)]
+pub struct With(u32);
+
+#[derive(Digestible, Clone, Copy)]
+#[digestible(crate = "framework", bytes)]
+#[repr(C)]
+pub struct Bytes {
+    pub a: u32,
+    pub b: u32,
+}
+
+fn main() {
+    let mut writer = Vec::new();
+    Struct {
+        id: 1,
+        port: 2,
+        hashed: "hashed".to_string(),
+        email: "user@example.com".to_string(),
+        phone: None,
+    }
+    .digest::<LittleEndian, _>(&mut writer);
+    Enum::B(1u8).digest::<LittleEndian, _>(&mut writer);
+    Transparent(1).digest::<LittleEndian, _>(&mut writer);
+    With(1).digest::<LittleEndian, _>(&mut writer);
+    Bytes { a: 1, b: 2 }.digest::<LittleEndian, _>(&mut writer);
+    assert!(!writer.is_empty());
+}